@@ -10,6 +10,7 @@
 
 use anslatortray::translate;
 use anslatortray::byte_string;
+use anslatortray::{PigLatinWriter, Style};
 
 /* Functions */
 
@@ -54,7 +55,7 @@ fn help() {
     eprintln!("\n{}", translate("Have a good day!"));
 }
 
-fn interactive(args: &Vec<String>) {
+fn interactive(args: &[String]) {
     if args.len() != 0 {
         eprintln!("Error: didn't expect any arguments");
         help();
@@ -74,7 +75,7 @@ fn interactive(args: &Vec<String>) {
     }
 }
 
-fn file(args: &Vec<String>) {
+fn file(args: &[String]) {
     eprintln!("Note: anslatortray --file is highly experimental and has poor error handling. You have been warned.");
 
     if args.len() != 2 {
@@ -98,7 +99,7 @@ fn file(args: &Vec<String>) {
     eprintln!("Sucessful: took {}ns to translate", time_to_translate.as_nanos());
 }
 
-fn benchmark_file(args: &Vec<String>) {
+fn benchmark_file(args: &[String]) {
     eprintln!("Note: anslatortray --benchmark-file is highly experimental and has poor error handling. You have been warned.");
 
     if args.len() != 2 {
@@ -138,9 +139,49 @@ fn benchmark_file(args: &Vec<String>) {
         std::fs::write("/dev/null", &translated_file_contents).unwrap();//TODO avoid needing unix
     }
     eprintln!("Sucessful: Byte-string translation with reused allocations took {}ns to translate on average over {} runs.", total_duration_byte_string.as_nanos() / iterations, iterations);
+
+    {
+        let expected_translation = translated_file_contents.clone();
+        let mut total_duration_cached = std::time::Duration::new(0, 0);
+        let mut translator = anslatortray::CachingTranslator::new(Style::WAY, anslatortray::DEFAULT_CACHE_CAPACITY);
+
+        for _ in 0..iterations {
+            let start_time = std::time::Instant::now();
+            translated_file_contents.truncate(0);
+            translator.translate_bytes(file_contents.as_bytes(), &mut translated_file_contents);
+            let time_to_translate = start_time.elapsed();
+            total_duration_cached += time_to_translate;
+            assert!(translated_file_contents == expected_translation, "Cached translation differs from the byte-string translation");
+        }
+        eprintln!("Sucessful: Cached translation took {}ns to translate on average over {} runs ({:.1}% of words hit the cache).",
+            total_duration_cached.as_nanos() / iterations, iterations, translator.stats().hit_rate() * 100.0
+        );
+    }
+
+    #[cfg(feature = "rayon")]
+    {
+        let expected_translation = translated_file_contents.clone();
+        let mut total_duration_parallel = std::time::Duration::new(0, 0);
+        let mut total_duration_slowest_chunk = std::time::Duration::new(0, 0);
+        let mut number_of_chunks = 0;
+
+        for _ in 0..iterations {
+            let start_time = std::time::Instant::now();
+            translated_file_contents.truncate(0);
+            let timings = anslatortray::translate_parallel(file_contents.as_bytes(), &Style::WAY, &mut translated_file_contents);
+            let time_to_translate = start_time.elapsed();
+            total_duration_parallel += time_to_translate;
+            total_duration_slowest_chunk += timings.iter().map(|timing| timing.duration).max().unwrap_or_default();
+            number_of_chunks = timings.len();
+            assert!(translated_file_contents == expected_translation, "Parallel translation differs from the byte-string translation");
+        }
+        eprintln!("Sucessful: Parallel translation took {}ns to translate on average over {} runs ({} chunks, the slowest taking {}ns on average).",
+            total_duration_parallel.as_nanos() / iterations, iterations, number_of_chunks, total_duration_slowest_chunk.as_nanos() / iterations
+        );
+    }
 }
 
-fn translate_args(args: &Vec<String>) {
+fn translate_args(args: &[String]) {
     if args.len() == 0 {
         eprintln!("Error: expected at least one string to translate");
         help();
@@ -149,27 +190,23 @@ fn translate_args(args: &Vec<String>) {
 
     //Translate the arguments and print them out for the user
     for string in args {
-        print!("{} ", translate(&string));
+        print!("{} ", translate(string));
     }
     println!();
 }
 
-fn stdin_to_stdout(args: &Vec<String>) {
-    use std::io::{Read, Write};
-
+fn stdin_to_stdout(args: &[String]) {
     if args.len() != 0 {
         eprintln!("Error: didn't expect any arguments");
         help();
         return;
     }
 
-    let mut stdin = std::io::stdin();
-    let mut stdout = std::io::stdout();
-    let mut buffer = String::new();
+    //Translate as the text flows through so we never need to hold all of stdin in memory
+    let stdin = std::io::stdin();
+    let stdout = std::io::stdout();
+    let mut writer = PigLatinWriter::new(std::io::BufWriter::new(stdout.lock()), Style::WAY);
 
-    while let Ok(bytes_read) = stdin.read_to_string(&mut buffer) {
-        if bytes_read == 0 { return; }
-        write!(stdout, "{}", translate(&buffer)).unwrap();//TODO do this more efficiently (avoid format string)
-        buffer.truncate(0);//TODO is this needed here?
-    }
+    std::io::copy(&mut stdin.lock(), &mut writer).unwrap();//TODO error handling
+    writer.finish().unwrap();
 }
@@ -0,0 +1,402 @@
+/* async_io.rs
+ * By: John Jekel
+ * Copyright (C) 2022 John Jekel
+ * See the LICENSE file at the root of the project for licensing info.
+ *
+ * Translation of text as it flows through tokio's AsyncRead and AsyncWrite, and Streams of Bytes
+ *
+*/
+
+/* Imports */
+
+use std::io;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use bytes::Bytes;
+use futures_core::Stream;
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+
+use crate::stream::ChunkTranslator;
+use crate::style::Style;
+
+/* Constants */
+
+//How much is read from the inner reader at once
+const READ_CHUNK_SIZE: usize = 8 * 1024;
+
+/* Types */
+
+///The async equivalent of [`crate::PigLatinReader`]: translates everything read from another [`AsyncRead`]er.
+///
+///Only the word that straddles the end of each chunk read from the inner reader is buffered.
+///The inner reader must be [`Unpin`]; use [`Box::pin()`] to wrap one that isn't.
+///
+///Requires the `tokio` feature.
+///
+///# Examples
+///
+///```
+///use tokio::io::AsyncReadExt;
+///use anslatortray::{AsyncPigLatinReader, Style};
+///
+///# tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+///let mut reader = AsyncPigLatinReader::new("Hello world!".as_bytes(), Style::WAY);
+///
+///let mut pig_latin = String::new();
+///reader.read_to_string(&mut pig_latin).await.unwrap();
+///assert_eq!(pig_latin, "Ellohay orldway!");
+///# });
+///```
+#[derive(Debug)]
+pub struct AsyncPigLatinReader<R: AsyncRead + Unpin> {
+    inner: R,
+    translator: ChunkTranslator,
+    input: Vec<u8>,
+    output: Vec<u8>,
+    output_index: usize,
+    finished: bool,
+}
+
+///The async equivalent of [`crate::PigLatinWriter`]: translates everything written to it, passing the translation on to another [`AsyncWrite`]r.
+///
+///Only the word that straddles the end of each write (and translated text the inner writer hasn't accepted yet) is buffered.
+///Since async writers can't write when they are dropped, you must shut it down (ex. with `AsyncWriteExt::shutdown()`) once done
+///to translate the last word; flushing alone leaves a word that may continue in the next write untranslated.
+///The inner writer must be [`Unpin`]; use [`Box::pin()`] to wrap one that isn't.
+///
+///Requires the `tokio` feature.
+///
+///# Examples
+///
+///```
+///use tokio::io::AsyncWriteExt;
+///use anslatortray::{AsyncPigLatinWriter, Style};
+///
+///# tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+///let mut writer = AsyncPigLatinWriter::new(Vec::<u8>::new(), Style::WAY);
+///writer.write_all(b"Hello wo").await.unwrap();
+///writer.write_all(b"rld!").await.unwrap();
+///writer.shutdown().await.unwrap();
+///
+///assert_eq!(writer.into_inner(), b"Ellohay orldway!");
+///# });
+///```
+#[derive(Debug)]
+pub struct AsyncPigLatinWriter<W: AsyncWrite + Unpin> {
+    inner: W,
+    translator: ChunkTranslator,
+    buffer: Vec<u8>,
+    buffer_index: usize,
+    finished: bool,
+}
+
+///A [`Stream`] of translated [`Bytes`] chunks; see [`translate_stream()`].
+///
+///Requires the `tokio` feature.
+#[derive(Debug)]
+pub struct PigLatinStream<S: Stream<Item = Bytes> + Unpin> {
+    inner: S,
+    translator: ChunkTranslator,
+    finished: bool,
+}
+
+/* Associated Functions and Methods */
+
+impl<R: AsyncRead + Unpin> AsyncPigLatinReader<R> {
+    ///Creates a new reader that translates text read from `inner` with the given [`Style`].
+    pub fn new(inner: R, style: Style) -> AsyncPigLatinReader<R> {
+        return AsyncPigLatinReader {
+            inner,
+            translator: ChunkTranslator::new(style),
+            input: vec![0; READ_CHUNK_SIZE],
+            output: Vec::with_capacity(READ_CHUNK_SIZE * 2),
+            output_index: 0,
+            finished: false,
+        };
+    }
+
+    ///Returns the inner reader.
+    pub fn into_inner(self) -> R {
+        return self.inner;
+    }
+}
+
+impl<R: AsyncRead + Unpin> AsyncRead for AsyncPigLatinReader<R> {
+    fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+
+        //Translate more text until we have something to give back (a chunk could be a single partial word)
+        while this.output_index == this.output.len() {
+            if this.finished {
+                return Poll::Ready(Ok(()));
+            }
+
+            this.output.truncate(0);
+            this.output_index = 0;
+
+            let mut input = ReadBuf::new(&mut this.input);
+            match Pin::new(&mut this.inner).poll_read(cx, &mut input) {
+                Poll::Ready(Ok(())) => {},
+                Poll::Ready(Err(error)) => { return Poll::Ready(Err(error)); },
+                Poll::Pending => { return Poll::Pending; },
+            }
+
+            if input.filled().len() == 0 {
+                this.translator.finish(&mut this.output);
+                this.finished = true;
+            } else {
+                this.translator.translate_chunk(input.filled(), &mut this.output);
+            }
+        }
+
+        let available = &this.output[this.output_index..];
+        let amount = available.len().min(buf.remaining());
+        buf.put_slice(&available[..amount]);
+        this.output_index += amount;
+        return Poll::Ready(Ok(()));
+    }
+}
+
+impl<W: AsyncWrite + Unpin> AsyncPigLatinWriter<W> {
+    ///Creates a new writer that translates with the given [`Style`] and writes the translation to `inner`.
+    pub fn new(inner: W, style: Style) -> AsyncPigLatinWriter<W> {
+        return AsyncPigLatinWriter { inner, translator: ChunkTranslator::new(style), buffer: Vec::new(), buffer_index: 0, finished: false };
+    }
+
+    ///Returns a reference to the inner writer.
+    pub fn get_ref(&self) -> &W {
+        return &self.inner;
+    }
+
+    ///Returns the inner writer. Any translated text that hasn't been written to it yet is lost.
+    pub fn into_inner(self) -> W {
+        return self.inner;
+    }
+
+    //Writes everything in the buffer to the inner writer
+    fn poll_write_buffer(&mut self, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        while self.buffer_index < self.buffer.len() {
+            match Pin::new(&mut self.inner).poll_write(cx, &self.buffer[self.buffer_index..]) {
+                Poll::Ready(Ok(0)) => { return Poll::Ready(Err(io::ErrorKind::WriteZero.into())); },
+                Poll::Ready(Ok(bytes_written)) => { self.buffer_index += bytes_written; },
+                Poll::Ready(Err(error)) => { return Poll::Ready(Err(error)); },
+                Poll::Pending => { return Poll::Pending; },
+            }
+        }
+
+        self.buffer.truncate(0);
+        self.buffer_index = 0;
+        return Poll::Ready(Ok(()));
+    }
+}
+
+impl<W: AsyncWrite + Unpin> AsyncWrite for AsyncPigLatinWriter<W> {
+    fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        let this = self.get_mut();
+
+        //Only accept more text once the translation of the previous text is written so the buffer stays small
+        match this.poll_write_buffer(cx) {
+            Poll::Ready(Ok(())) => {},
+            Poll::Ready(Err(error)) => { return Poll::Ready(Err(error)); },
+            Poll::Pending => { return Poll::Pending; },
+        }
+
+        this.translator.translate_chunk(buf, &mut this.buffer);
+
+        //Get a head start on writing the translation. The text was already accepted, so if the inner writer
+        //isn't ready (or fails) we leave what's left in the buffer and try again (reporting any error) on the next call
+        let _ = this.poll_write_buffer(cx);
+        return Poll::Ready(Ok(buf.len()));
+    }
+
+    ///Writes all translated text to the inner writer and flushes it.
+    ///
+    ///Note that if the text written so far ends in the middle of a word, that word is held back until more text is written or the writer is shut down.
+    fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        match this.poll_write_buffer(cx) {
+            Poll::Ready(Ok(())) => {},
+            Poll::Ready(Err(error)) => { return Poll::Ready(Err(error)); },
+            Poll::Pending => { return Poll::Pending; },
+        }
+        return Pin::new(&mut this.inner).poll_flush(cx);
+    }
+
+    ///Translates the last word (if the text ended in the middle of one), writes all translated text, and shuts down the inner writer.
+    fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        let this = self.get_mut();
+        if !this.finished {
+            this.translator.finish(&mut this.buffer);
+            this.finished = true;
+        }
+
+        match this.poll_write_buffer(cx) {
+            Poll::Ready(Ok(())) => {},
+            Poll::Ready(Err(error)) => { return Poll::Ready(Err(error)); },
+            Poll::Pending => { return Poll::Pending; },
+        }
+        return Pin::new(&mut this.inner).poll_shutdown(cx);
+    }
+}
+
+impl<S: Stream<Item = Bytes> + Unpin> PigLatinStream<S> {
+    ///Returns the inner stream.
+    pub fn into_inner(self) -> S {
+        return self.inner;
+    }
+}
+
+impl<S: Stream<Item = Bytes> + Unpin> Stream for PigLatinStream<S> {
+    type Item = Bytes;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Bytes>> {
+        let this = self.get_mut();
+
+        loop {
+            if this.finished {
+                return Poll::Ready(None);
+            }
+
+            let mut pig_latin = Vec::new();
+            match Pin::new(&mut this.inner).poll_next(cx) {
+                Poll::Ready(Some(chunk)) => {
+                    pig_latin.reserve(chunk.len() * 2);
+                    this.translator.translate_chunk(&chunk, &mut pig_latin);
+                },
+                Poll::Ready(None) => {
+                    this.translator.finish(&mut pig_latin);
+                    this.finished = true;
+                },
+                Poll::Pending => { return Poll::Pending; },
+            }
+
+            //Don't yield empty chunks (ex. if the chunk was just part of a word)
+            if pig_latin.len() != 0 {
+                return Poll::Ready(Some(Bytes::from(pig_latin)));
+            }
+        }
+    }
+}
+
+/* Functions */
+
+///Translates a [`Stream`] of [`Bytes`] (ex. an HTTP body) chunk by chunk, without waiting for the whole stream!
+///
+///Words split between chunks are translated correctly: only the word that straddles the end of each chunk is held back until the next one arrives.
+///Empty chunks are never yielded.
+///
+///Requires the `tokio` feature.
+///
+///# Examples
+///
+///```
+///use std::future::poll_fn;
+///use std::pin::Pin;
+///use std::task::{Context, Poll};
+///use bytes::Bytes;
+///use futures_core::Stream;
+///use anslatortray::{translate_stream, Style};
+///
+///# tokio::runtime::Builder::new_current_thread().build().unwrap().block_on(async {
+///let chunks = [Bytes::from("Hello wo"), Bytes::from("rld!")];
+///let mut pig_latin = translate_stream(StreamOf(chunks.into_iter()), Style::WAY);
+///
+///let mut translated = Vec::new();
+///while let Some(chunk) = poll_fn(|cx| Pin::new(&mut pig_latin).poll_next(cx)).await {
+///    translated.extend_from_slice(&chunk);
+///}
+///assert_eq!(translated, b"Ellohay orldway!");
+///# });
+///
+/////A minimal stream over an iterator (in practice, use your HTTP library's body stream)
+///struct StreamOf<I>(I);
+///impl<I: Iterator<Item = Bytes> + Unpin> Stream for StreamOf<I> {
+///    type Item = Bytes;
+///    fn poll_next(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<Bytes>> {
+///        return Poll::Ready(self.0.next());
+///    }
+///}
+///```
+pub fn translate_stream<S: Stream<Item = Bytes> + Unpin>(stream: S, style: Style) -> PigLatinStream<S> {
+    return PigLatinStream { inner: stream, translator: ChunkTranslator::new(style), finished: false };
+}
+
+/* Tests */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::task::Waker;
+
+    const ENGLISH: &str = "Let's try some edge cases: ISn't THIS COOL? naïve mcDONALD's, bcd 123 Hyphens-are-difficult-aren't-they?' Ok";
+
+    //Everything in these tests is in memory, so polling never returns Pending and we don't need a runtime
+    fn ready<T>(poll: Poll<T>) -> T {
+        return match poll {
+            Poll::Ready(value) => value,
+            Poll::Pending => panic!("unexpected Poll::Pending"),
+        };
+    }
+
+    struct ChunkStream(Vec<Bytes>);
+
+    impl Stream for ChunkStream {
+        type Item = Bytes;
+
+        fn poll_next(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<Bytes>> {
+            let chunks = &mut self.get_mut().0;
+            return Poll::Ready(if chunks.len() == 0 { None } else { Some(chunks.remove(0)) });
+        }
+    }
+
+    #[test]
+    fn test_async_pig_latin_reader() {
+        let mut cx = Context::from_waker(Waker::noop());
+        let mut reader = AsyncPigLatinReader::new(ENGLISH.as_bytes(), Style::WAY);
+
+        let mut pig_latin = Vec::new();
+        let mut buf = [0u8; 5];
+        loop {
+            let mut read_buf = ReadBuf::new(&mut buf);
+            ready(Pin::new(&mut reader).poll_read(&mut cx, &mut read_buf)).unwrap();
+            if read_buf.filled().len() == 0 {
+                break;
+            }
+            pig_latin.extend_from_slice(read_buf.filled());
+        }
+        assert_eq!(String::from_utf8(pig_latin).unwrap(), crate::translate(ENGLISH));
+    }
+
+    #[test]
+    fn test_async_pig_latin_writer() {
+        let mut cx = Context::from_waker(Waker::noop());
+
+        for chunk_size in [1, 2, 3, 7, ENGLISH.len()] {
+            let mut writer = AsyncPigLatinWriter::new(Vec::new(), Style::FERB);
+            for chunk in ENGLISH.as_bytes().chunks(chunk_size) {
+                assert_eq!(ready(Pin::new(&mut writer).poll_write(&mut cx, chunk)).unwrap(), chunk.len());
+            }
+            ready(Pin::new(&mut writer).poll_shutdown(&mut cx)).unwrap();
+            assert_eq!(String::from_utf8(writer.into_inner()).unwrap(), crate::translate_ferb(ENGLISH));
+        }
+    }
+
+    #[test]
+    fn test_translate_stream() {
+        let mut cx = Context::from_waker(Waker::noop());
+
+        for chunk_size in [1, 4, 9, ENGLISH.len()] {
+            let chunks = ENGLISH.as_bytes().chunks(chunk_size).map(Bytes::copy_from_slice).collect();
+            let mut stream = translate_stream(ChunkStream(chunks), Style::WAY);
+
+            let mut pig_latin = Vec::new();
+            while let Some(chunk) = ready(Pin::new(&mut stream).poll_next(&mut cx)) {
+                assert_ne!(chunk.len(), 0);
+                pig_latin.extend_from_slice(&chunk);
+            }
+            assert_eq!(String::from_utf8(pig_latin).unwrap(), crate::translate(ENGLISH));
+            assert_eq!(ready(Pin::new(&mut stream).poll_next(&mut cx)), None);
+        }
+    }
+}
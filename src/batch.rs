@@ -0,0 +1,197 @@
+/* batch.rs
+ * By: John Jekel
+ * Copyright (C) 2022 John Jekel
+ * See the LICENSE file at the root of the project for licensing info.
+ *
+ * Translation of many strings into one shared buffer
+ *
+*/
+
+/* Imports */
+
+use core::ops::Range;
+
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::byte_string::translate_with as translate_byte_string_with;
+use crate::style::Style;
+use crate::utf8::translated_str;
+
+/* Types */
+
+///The translations of many strings, stored back to back in one buffer; see [`translate_batch()`].
+///
+///Translating a batch only allocates when the buffer or offset table needs to grow, instead of once per string.
+///A batch can also be [`TranslatedBatch::clear()`]ed and refilled with [`TranslatedBatch::push_with()`] to reuse its allocations across runs.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TranslatedBatch {
+    text: Vec<u8>,//Always valid UTF-8 (see push_with())
+    //Where each translation starts, plus one more for the end of the last one
+    offsets: Vec<usize>,
+}
+
+/* Associated Functions and Methods */
+
+impl TranslatedBatch {
+    ///Creates an empty batch.
+    pub fn new() -> TranslatedBatch {
+        return TranslatedBatch { text: Vec::new(), offsets: vec![0] };
+    }
+
+    ///Translates a multi-word string (including punctuation) using a [`Style`], adding it to the end of the batch.
+    pub fn push_with(&mut self, english: &str, style: &Style) {
+        let start = self.text.len();
+        translate_byte_string_with(english.as_bytes(), style, &mut self.text);
+        translated_str(&self.text[start..]);//Only the new translation needs to be checked with the safe feature
+        self.offsets.push(self.text.len());
+    }
+
+    ///Returns the translation of the string at `index`, or [`None`] if it is out of bounds.
+    pub fn get(&self, index: usize) -> Option<&str> {
+        return Some(translated_str(&self.text[self.range(index)?]));
+    }
+
+    ///Returns the byte range of the translation of the string at `index` within [`TranslatedBatch::as_str()`], or [`None`] if it is out of bounds.
+    pub fn range(&self, index: usize) -> Option<Range<usize>> {
+        if index >= self.len() {
+            return None;
+        }
+        return Some(self.offsets[index]..self.offsets[index + 1]);
+    }
+
+    ///Returns the number of translations in the batch.
+    pub fn len(&self) -> usize {
+        return self.offsets.len() - 1;
+    }
+
+    ///Returns true if the batch contains no translations.
+    pub fn is_empty(&self) -> bool {
+        return self.len() == 0;
+    }
+
+    ///Returns every translation in the batch concatenated together.
+    pub fn as_str(&self) -> &str {
+        return translated_str(&self.text);
+    }
+
+    ///Returns an iterator over the translations in the batch, in order.
+    pub fn iter(&self) -> impl ExactSizeIterator<Item = &str> + '_ {
+        return self.offsets.windows(2).map(|bounds| translated_str(&self.text[bounds[0]..bounds[1]]));
+    }
+
+    ///Removes every translation from the batch, keeping the allocated buffers for reuse.
+    pub fn clear(&mut self) {
+        self.text.truncate(0);
+        self.offsets.truncate(1);
+    }
+}
+
+impl Default for TranslatedBatch {
+    fn default() -> TranslatedBatch {
+        return TranslatedBatch::new();
+    }
+}
+
+impl core::ops::Index<usize> for TranslatedBatch {
+    type Output = str;
+
+    fn index(&self, index: usize) -> &str {
+        return match self.get(index) {
+            Some(translation) => translation,
+            None => panic!("index {} is out of bounds for a batch of {} translations", index, self.len()),
+        };
+    }
+}
+
+/* Functions */
+
+///Translates many multi-word strings (including punctuation) into one shared buffer!
+///
+///This is the same as calling [`crate::translate()`] on each string, but every translation is written back to back into
+///a single buffer instead of being allocated separately, which is much cheaper for large numbers of short strings.
+///
+///# Examples
+///
+///```
+///use anslatortray::translate_batch;
+///
+///let batch = translate_batch(&["Open", "Save as...", "Quit"]);
+///
+///assert_eq!(batch.len(), 3);
+///assert_eq!(&batch[0], "Openway");
+///assert_eq!(batch.get(1), Some("Avesay asway..."));
+///assert_eq!(batch.get(3), None);
+///assert_eq!(batch.iter().collect::<Vec<_>>(), ["Openway", "Avesay asway...", "Uitqay"]);
+///assert_eq!(batch.as_str(), "OpenwayAvesay asway...Uitqay");
+///```
+pub fn translate_batch(inputs: &[&str]) -> TranslatedBatch {
+    return translate_batch_with(inputs, &Style::WAY);
+}
+
+///Translates many multi-word strings (including punctuation) into one shared buffer using a [`Style`]; see [`translate_batch()`].
+///
+///# Examples
+///
+///```
+///use anslatortray::{translate_batch_with, Style};
+///
+///let batch = translate_batch_with(&["Where's Perry?", "Hello"], &Style::FERB);
+///assert_eq!(&batch[0], "Erewherb's Erryperb?");
+///assert_eq!(&batch[1], "Elloherb");
+///```
+pub fn translate_batch_with(inputs: &[&str], style: &Style) -> TranslatedBatch {
+    let total_len: usize = inputs.iter().map(|english| english.len()).sum();
+
+    let mut batch = TranslatedBatch {
+        text: Vec::with_capacity(total_len * 2),//Plenty of headroom in case the words are very small or the suffixes are long
+        offsets: Vec::with_capacity(inputs.len() + 1),
+    };
+    batch.offsets.push(0);
+
+    for english in inputs {
+        batch.push_with(english, style);
+    }
+    return batch;
+}
+
+/* Tests */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::style::RulesVersion;
+
+    const INPUTS: [&str; 6] = ["Let's try some edge cases:", "", "ISn't THIS COOL?", "naïve mcDONALD's, bcd 123", "a", "Hyphens-are-difficult-aren't-they?'"];
+
+    #[test]
+    fn test_translate_batch_matches_translate_with() {
+        for style in [Style::WAY, Style::FERB, Style::new("äy", "wäy"), Style::HAY.with_rules(RulesVersion::V0_4)] {
+            let batch = translate_batch_with(&INPUTS, &style);
+            assert_eq!(batch.len(), INPUTS.len());
+
+            let expected: Vec<String> = INPUTS.iter().map(|english| crate::translate_with(english, &style)).collect();
+            assert_eq!(batch.iter().collect::<Vec<_>>(), expected);
+            assert_eq!(batch.as_str(), expected.concat());
+            for (index, translation) in expected.iter().enumerate() {
+                assert_eq!(&batch[index], translation);
+            }
+        }
+    }
+
+    #[test]
+    fn test_translated_batch_reuse() {
+        let mut batch = TranslatedBatch::new();
+        assert!(batch.is_empty());
+        assert_eq!(batch.get(0), None);
+
+        batch.push_with("Hello", &Style::WAY);
+        batch.push_with("world", &Style::WAY);
+        assert_eq!(batch.range(1), Some(7..14));
+
+        batch.clear();
+        assert!(batch.is_empty());
+        batch.push_with("Again", &Style::WAY);
+        assert_eq!(batch.iter().collect::<Vec<_>>(), ["Againway"]);
+    }
+}
@@ -11,6 +11,8 @@
 //!
 //!As opposed to functions provided in the anslatortray crate's root, which operate on [`&str`] and [`String`], these functions operate on `&[u8]` and [`Vec<u8>`].
 //!
+//![`translate_to_slice()`] and [`translated_len()`] don't allocate at all, so they are available even without the `alloc` feature (ex. in `no_std` firmware).
+//!
 //!In performance-sensitive applications, they can allow for some minor optimizations:
 //!* One can reuse buffers for getting the result of a translation (as the functions accept a mutable reference to a [`Vec<u8>`] rather than returning data)
 //!* One can avoid the penalty of converting to an [`&str`], translating to a [`String`], and having to convert back to raw bytes if one is working solely with byte-strings.
@@ -23,7 +25,115 @@
 
 /* Imports */
 
-use std::num::Wrapping;
+use core::num::Wrapping;
+use crate::classes::ByteClasses;
+use crate::style::{Style, RulesVersion, WordRules};
+use crate::word::{Rule, CaseClass, WordAnalysis};
+use crate::scan::{find_letter, find_non_letter};
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "alloc")]
+use crate::spans::{Spans, SpanKind};
+#[cfg(feature = "alloc")]
+use crate::diagnostics::{Diagnostic, check_word};
+
+/* Types */
+
+///The error returned by [`translate_to_slice()`] when the translation doesn't fit in the buffer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct BufferTooSmall {
+    ///How many bytes the buffer would need to hold the whole translation (see [`translated_len()`])
+    pub required: usize,
+}
+
+///The error returned by [`try_translate()`] when the input isn't valid UTF-8.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct InvalidUtf8 {
+    ///The offset of the first invalid sequence (everything before it is valid UTF-8)
+    pub valid_up_to: usize,
+    ///The length of the invalid sequence, or [`None`] if the input ended in the middle of a character (like [`core::str::Utf8Error::error_len()`])
+    pub error_len: Option<usize>,
+}
+
+//Somewhere the translation loop can append bytes to
+pub(crate) trait Output {
+    fn push(&mut self, byte: u8);
+    fn extend_from_slice(&mut self, bytes: &[u8]);
+}
+
+//Writes into a fixed-size slice, counting (but not writing) any bytes that don't fit
+struct SliceOutput<'a> {
+    slice: &'a mut [u8],
+    len: usize,
+}
+
+//Only counts how many bytes would be written
+struct LenOutput(usize);
+
+/* Associated Functions and Methods */
+
+impl core::fmt::Display for BufferTooSmall {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        return write!(f, "buffer too small for translation ({} bytes required)", self.required);
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for BufferTooSmall {}
+
+impl core::fmt::Display for InvalidUtf8 {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        return write!(f, "invalid UTF-8 at offset {}", self.valid_up_to);
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidUtf8 {}
+
+#[cfg(feature = "alloc")]
+impl Output for Vec<u8> {
+    #[inline(always)]
+    fn push(&mut self, byte: u8) {
+        Vec::push(self, byte);
+    }
+
+    #[inline(always)]
+    fn extend_from_slice(&mut self, bytes: &[u8]) {
+        Vec::extend_from_slice(self, bytes);
+    }
+}
+
+impl Output for SliceOutput<'_> {
+    #[inline(always)]
+    fn push(&mut self, byte: u8) {
+        if let Some(destination) = self.slice.get_mut(self.len) {
+            *destination = byte;
+        }
+        self.len += 1;
+    }
+
+    #[inline(always)]
+    fn extend_from_slice(&mut self, bytes: &[u8]) {
+        let end = self.len + bytes.len();
+        if end <= self.slice.len() {
+            self.slice[self.len..end].copy_from_slice(bytes);
+        }
+        self.len = end;
+    }
+}
+
+impl Output for LenOutput {
+    #[inline(always)]
+    fn push(&mut self, _: u8) {
+        self.0 += 1;
+    }
+
+    #[inline(always)]
+    fn extend_from_slice(&mut self, bytes: &[u8]) {
+        self.0 += bytes.len();
+    }
+}
 
 /* Functions */
 
@@ -74,6 +184,7 @@ use std::num::Wrapping;
 ///translate(b"so we can do something like this if we wish!", &mut buffer);
 ///assert_eq!(&buffer, b"Ethay ufferbay isnway't earedclay ybay ethay anslatetray unctionfay eforehandbay, osay eway ancay oday omethingsay ikelay isthay ifway eway ishway!");
 ///```
+#[cfg(feature = "alloc")]
 pub fn translate(english: &[u8], pig_latin_string: &mut Vec::<u8>) {
     translate_way(english, pig_latin_string);
 }
@@ -123,8 +234,9 @@ pub fn translate(english: &[u8], pig_latin_string: &mut Vec::<u8>) {
 ///translate_way(b"so we can do something like this if we wish!", &mut buffer);
 ///assert_eq!(&buffer, b"Ethay ufferbay isnway't earedclay ybay ethay anslatetray unctionfay eforehandbay, osay eway ancay oday omethingsay ikelay isthay ifway eway ishway!");
 ///```
+#[cfg(feature = "alloc")]
 pub fn translate_way(english: &[u8], pig_latin_string: &mut Vec::<u8>) {
-    translate_with_style_lower_and_upper_suffixes(english, b"ay", b"way", b"AY", b"WAY", pig_latin_string);
+    translate_with_style_lower_and_upper_suffixes(english, WordRules::LATEST, b"ay", b"way", b"AY", b"WAY", pig_latin_string);
 }
 
 ///Translates a multi-word string (including punctuation) into Pig Latin (yay-style)!
@@ -172,8 +284,9 @@ pub fn translate_way(english: &[u8], pig_latin_string: &mut Vec::<u8>) {
 ///translate_yay(b"so we can do something like this if we wish!", &mut buffer);
 ///assert_eq!(&buffer, b"Ethay ufferbay isnyay't earedclay ybay ethay anslatetray unctionfay eforehandbay, osay eway ancay oday omethingsay ikelay isthay ifyay eway ishway!");
 ///```
+#[cfg(feature = "alloc")]
 pub fn translate_yay(english: &[u8], pig_latin_string: &mut Vec::<u8>) {
-    translate_with_style_lower_and_upper_suffixes(english, b"ay", b"yay", b"AY", b"WAY", pig_latin_string);
+    translate_with_style_lower_and_upper_suffixes(english, WordRules::LATEST, b"ay", b"yay", b"AY", b"YAY", pig_latin_string);
 }
 
 ///Translates a multi-word string (including punctuation) into Pig Latin (hay-style)!
@@ -221,8 +334,9 @@ pub fn translate_yay(english: &[u8], pig_latin_string: &mut Vec::<u8>) {
 ///translate_hay(b"so we can do something like this if we wish!", &mut buffer);
 ///assert_eq!(&buffer, b"Ethay ufferbay isnhay't earedclay ybay ethay anslatetray unctionfay eforehandbay, osay eway ancay oday omethingsay ikelay isthay ifhay eway ishway!");
 ///```
+#[cfg(feature = "alloc")]
 pub fn translate_hay(english: &[u8], pig_latin_string: &mut Vec::<u8>) {
-    translate_with_style_lower_and_upper_suffixes(english, b"ay", b"hay", b"AY", b"HAY", pig_latin_string);
+    translate_with_style_lower_and_upper_suffixes(english, WordRules::LATEST, b"ay", b"hay", b"AY", b"HAY", pig_latin_string);
 }
 
 ///Translates a multi-word string (including punctuation) into Ferb Latin!
@@ -270,8 +384,9 @@ pub fn translate_hay(english: &[u8], pig_latin_string: &mut Vec::<u8>) {
 ///translate_ferb(b"so we can do something like this if we wish!", &mut buffer);
 ///assert_eq!(&buffer, b"Etherb ufferberb isnferb't earedclerb yberb etherb anslatetrerb unctionferb eforehandberb, oserb ewerb ancerb oderb omethingserb ikelerb istherb ifferb ewerb ishwerb!");
 ///```
+#[cfg(feature = "alloc")]
 pub fn translate_ferb(english: &[u8], pig_latin_string: &mut Vec::<u8>) {
-    translate_with_style_lower_and_upper_suffixes(english, b"erb", b"ferb", b"ERB", b"FERB", pig_latin_string);
+    translate_with_style_lower_and_upper_suffixes(english, WordRules::LATEST, b"erb", b"ferb", b"ERB", b"FERB", pig_latin_string);
 }
 
 ///Translates a multi-word string (including punctuation) into a custom-styled play language!
@@ -325,6 +440,7 @@ pub fn translate_ferb(english: &[u8], pig_latin_string: &mut Vec::<u8>) {
 ///translate_with_style(b"so we can do something like this if we wish!", suffix, special_case_suffix, &mut buffer);
 ///assert_eq!(&buffer, b"Ethancy ufferbancy isnfancy't earedclancy ybancy ethancy anslatetrancy unctionfancy eforehandbancy, osancy ewancy ancancy odancy omethingsancy ikelancy isthancy iffancy ewancy ishwancy!");
 ///```
+#[cfg(feature = "alloc")]
 pub fn translate_with_style(english: &[u8], suffix_lower: &[u8], special_case_suffix_lower: &[u8], pig_latin_string: &mut Vec::<u8>) {
     //Convert the suffix and special_case_suffix we were provided to uppercase for words that are capitalized
     let mut suffix_upper = Vec::<u8>::with_capacity(suffix_lower.len());
@@ -336,37 +452,228 @@ pub fn translate_with_style(english: &[u8], suffix_lower: &[u8], special_case_su
         special_case_suffix_upper.push(letter.to_ascii_uppercase());//NOTE: We can't use fast_to_ascii_uppercase in case the suffixes contain UTF-8 or non-letters
     }
 
-    translate_with_style_lower_and_upper_suffixes(english, suffix_lower, special_case_suffix_lower, &suffix_upper, &special_case_suffix_upper, pig_latin_string);
+    translate_with_style_lower_and_upper_suffixes(english, WordRules::LATEST, suffix_lower, special_case_suffix_lower, &suffix_upper, &special_case_suffix_upper, pig_latin_string);
+}
+
+///Translates a multi-word string (including punctuation) using the suffixes and rules of a [`Style`]!
+///
+///Use this with [`Style::with_rules()`] if you need the output to stay exactly the same across anslatortray releases.
+///
+///Note: The resulting translation is appended to the provided buffer, so one may wish to ensure it is cleared before each use or not depending on the application.
+///
+///# Examples
+///
+///```
+///use anslatortray::byte_string::translate_with;
+///use anslatortray::{Style, RulesVersion};
+///
+///let mut buffer = Vec::<u8>::new();
+///
+///translate_with(b"Hello world from the coolest Pig Latin translator!", &Style::FERB, &mut buffer);
+///assert_eq!(&buffer, b"Elloherb orldwerb omfrerb etherb oolestcerb Igperb Atinlerb anslatortrerb!");
+///
+///buffer.truncate(0);
+///translate_with(b"What ABOUT a MIX?", &Style::YAY.with_rules(RulesVersion::V0_5), &mut buffer);
+///assert_eq!(&buffer, b"Atwhay ABOUTYAY ayay IXMAY?");
+///
+///buffer.truncate(0);
+///translate_with(b"mcDONALD", &Style::WAY.with_rules(RulesVersion::V0_4), &mut buffer);
+///assert_eq!(&buffer, b"ONALDmcDAY");
+///```
+#[cfg(feature = "alloc")]
+pub fn translate_with(english: &[u8], style: &Style, pig_latin_string: &mut Vec::<u8>) {
+    translate_with_style_lower_and_upper_suffixes (
+        english, style.word_rules(),
+        style.suffix_lower(), style.special_case_suffix_lower(), style.suffix_upper(), style.special_case_suffix_upper(),
+        pig_latin_string
+    );
+}
+
+///Translates a multi-word string (including punctuation) like [`translate_with()`], and also reports words whose translation relied on a heuristic!
+///
+///A [`Diagnostic`] is appended to `diagnostics` for every spot that may be worth double-checking by hand (see [`crate::DiagnosticKind`]).
+///Its `pig_latin` range indexes into `pig_latin_string` as a whole (including anything that was already in the buffer beforehand).
+///
+///Note: The resulting translation is appended to the provided buffer, so one may wish to ensure it is cleared before each use or not depending on the application.
+///
+///# Examples
+///
+///```
+///use anslatortray::byte_string::translate_with_diagnostics;
+///use anslatortray::{Style, DiagnosticKind};
+///
+///let mut buffer = Vec::<u8>::new();
+///let mut diagnostics = Vec::new();
+///
+///translate_with_diagnostics(b"Psst, it isn't NASA!", &Style::WAY, &mut buffer, &mut diagnostics);
+///assert_eq!(&buffer, b"Psstay, itway isnway't ASANAY!");
+///
+///assert_eq!(diagnostics.len(), 3);
+///assert_eq!(diagnostics[0].kind, DiagnosticKind::NoVowel);
+///assert_eq!(diagnostics[0].english, 0..4);
+///assert_eq!(diagnostics[1].kind, DiagnosticKind::ContractionSuffix);
+///assert_eq!(&buffer[diagnostics[1].pig_latin.clone()], b"isnway't");
+///assert_eq!(diagnostics[2].kind, DiagnosticKind::AcronymLike);
+///```
+#[cfg(feature = "alloc")]
+pub fn translate_with_diagnostics(english: &[u8], style: &Style, pig_latin_string: &mut Vec::<u8>, diagnostics: &mut Vec<Diagnostic>) {
+    let mut spans = Spans::new(english, style.classes()).peekable();
+    while let Some(span) = spans.next() {
+        if span.kind != SpanKind::Word {
+            pig_latin_string.extend_from_slice(&english[span.range]);
+            continue;
+        }
+
+        //A slice containing only a word translates the same as that word does in context
+        let pig_latin_start = pig_latin_string.len();
+        translate_with(&english[span.range.clone()], style, pig_latin_string);
+
+        //Keep the contraction suffix (if present) together with the word it belongs to
+        let contraction_suffix = spans.next_if(|next_span| next_span.kind == SpanKind::ContractionSuffix);
+        if let Some(contraction_suffix) = &contraction_suffix {
+            pig_latin_string.extend_from_slice(&english[contraction_suffix.range.clone()]);
+        }
+
+        check_word(english, &span, contraction_suffix.as_ref(), pig_latin_start..pig_latin_string.len(), style.classes(), diagnostics);
+    }
+}
+
+///Translates a multi-word string (including punctuation) like [`translate()`], but only if it is valid UTF-8!
+///
+///Since translation only ever rearranges ASCII letters (and copies everything else as-is), the translation is valid UTF-8 exactly when the input is,
+///so after this succeeds the newly appended bytes can be turned into a [`str`] with [`core::str::from_utf8_unchecked()`] without checking them again.
+///If the input is invalid, [`InvalidUtf8`] reports the offset of the first invalid sequence and nothing is appended.
+///
+///The whole input is validated up-front rather than piece by piece as it is translated: the standard library's validator is faster
+///over one long slice than over every short run of non-letters, and this way nothing has to be undone when an error is found.
+///
+///Note: The resulting translation is appended to the provided buffer, so one may wish to ensure it is cleared before each use or not depending on the application.
+///
+///# Examples
+///
+///```
+///use anslatortray::byte_string::{try_translate, InvalidUtf8};
+///
+///let mut buffer = Vec::<u8>::new();
+///try_translate("Hello naïve world!".as_bytes(), &mut buffer).unwrap();
+///assert_eq!(std::str::from_utf8(&buffer).unwrap(), "Ellohay anayïevay orldway!");
+///
+///let mut buffer = b"> ".to_vec();
+///assert_eq!(try_translate(b"Hello \xFFworld", &mut buffer), Err(InvalidUtf8 { valid_up_to: 6, error_len: Some(1) }));
+///assert_eq!(buffer, b"> ");
+///```
+#[cfg(feature = "alloc")]
+pub fn try_translate(english: &[u8], pig_latin_string: &mut Vec::<u8>) -> Result<(), InvalidUtf8> {
+    return try_translate_with(english, &Style::WAY, pig_latin_string);
+}
+
+///Translates a multi-word string (including punctuation) using a [`Style`] like [`translate_with()`], but only if it is valid UTF-8!
+///
+///See [`try_translate()`] for details.
+///
+///# Examples
+///
+///```
+///use anslatortray::byte_string::{try_translate_with, InvalidUtf8};
+///use anslatortray::Style;
+///
+///let mut buffer = Vec::<u8>::new();
+///try_translate_with(b"Where's Perry?", &Style::FERB, &mut buffer).unwrap();
+///assert_eq!(&buffer, b"Erewherb's Erryperb?");
+///
+/////A character cut off at the end of the input
+///assert_eq!(try_translate_with(b"caf\xC3", &Style::FERB, &mut buffer), Err(InvalidUtf8 { valid_up_to: 3, error_len: None }));
+///```
+#[cfg(feature = "alloc")]
+pub fn try_translate_with(english: &[u8], style: &Style, pig_latin_string: &mut Vec::<u8>) -> Result<(), InvalidUtf8> {
+    if let Err(error) = core::str::from_utf8(english) {
+        return Err(InvalidUtf8 { valid_up_to: error.valid_up_to(), error_len: error.error_len() });
+    }
+
+    translate_with(english, style, pig_latin_string);
+    return Ok(());
+}
+
+///Returns exactly how many bytes translating a multi-word string (including punctuation) with a [`Style`] produces, without translating it.
+///
+///Useful for sizing a buffer for [`translate_to_slice()`] (or reserving space in a [`Vec<u8>`] before calling [`translate_with()`]).
+///
+///# Examples
+///
+///```
+///use anslatortray::byte_string::translated_len;
+///use anslatortray::Style;
+///
+///assert_eq!(translated_len(b"Hello world!", &Style::WAY), b"Ellohay orldway!".len());
+///assert_eq!(translated_len(b"Where's Perry?", &Style::FERB), b"Erewherb's Erryperb?".len());
+///assert_eq!(translated_len(b"123", &Style::WAY), 3);
+///```
+pub fn translated_len(english: &[u8], style: &Style) -> usize {
+    let mut len = LenOutput(0);
+    translate_with_style_lower_and_upper_suffixes (
+        english, style.word_rules(),
+        style.suffix_lower(), style.special_case_suffix_lower(), style.suffix_upper(), style.special_case_suffix_upper(),
+        &mut len
+    );
+    return len.0;
+}
+
+///Translates a multi-word string (including punctuation) using a [`Style`] into a fixed-size buffer, returning the length of the translation!
+///
+///This never allocates, so it can be used to translate into preallocated or shared memory.
+///If the translation doesn't fit, [`BufferTooSmall`] is returned with the exact size needed, and the contents of the buffer are unspecified.
+///
+///# Examples
+///
+///```
+///use anslatortray::byte_string::{translate_to_slice, BufferTooSmall};
+///use anslatortray::Style;
+///
+///let mut buffer = [0u8; 32];
+///let len = translate_to_slice(b"Hello world!", &Style::WAY, &mut buffer).unwrap();
+///assert_eq!(&buffer[..len], b"Ellohay orldway!");
+///
+///let mut too_small = [0u8; 8];
+///assert_eq!(translate_to_slice(b"Hello world!", &Style::WAY, &mut too_small), Err(BufferTooSmall { required: 16 }));
+///```
+pub fn translate_to_slice(english: &[u8], style: &Style, pig_latin_string: &mut [u8]) -> Result<usize, BufferTooSmall> {
+    let capacity = pig_latin_string.len();
+    let mut output = SliceOutput { slice: pig_latin_string, len: 0 };
+    translate_with_style_lower_and_upper_suffixes (
+        english, style.word_rules(),
+        style.suffix_lower(), style.special_case_suffix_lower(), style.suffix_upper(), style.special_case_suffix_upper(),
+        &mut output
+    );
+
+    if output.len > capacity {
+        return Err(BufferTooSmall { required: output.len });
+    }
+    return Ok(output.len);
 }
 
 //Avoids the overhead of having to convert suffixes to uppercase for the standard translation functions at runtime
-pub(crate) fn translate_with_style_lower_and_upper_suffixes (
-    english: &[u8],
+pub(crate) fn translate_with_style_lower_and_upper_suffixes<O: Output> (
+    english: &[u8], rules: WordRules<'_>,
     suffix_lower: &[u8], special_case_suffix_lower: &[u8], suffix_upper: &[u8], special_case_suffix_upper: &[u8],
-    pig_latin_string: &mut Vec::<u8>
+    pig_latin_string: &mut O
 ) {
     if english.is_empty() {
         return;
     }
 
+    let classes = rules.classes;
     let mut global_index: usize = 0;
     loop {
-        //Copies characters in-between words
-        //TODO this could probably be optimized with vector instructions
+        //Copies characters in-between words (scanning with vector instructions if possible)
         {
-            let mut start_of_in_between_words_index: usize = global_index;//Inclusive
-            loop {
-                if english[global_index].is_ascii_alphabetic() {//Start of a word
-                    break;
-                }
-
-                global_index += 1;
-                if global_index == english.len() {
-                    //Copy all of the characters so far (all that remain) and return
-                    let remaining_characters_slice = &english[start_of_in_between_words_index..];
-                    pig_latin_string.extend_from_slice(remaining_characters_slice);
-                    return;
-                }
+            let start_of_in_between_words_index: usize = global_index;//Inclusive
+            if let Some(start_of_word_offset) = find_letter(&english[global_index..], classes) {
+                global_index += start_of_word_offset;
+            } else {
+                //Copy all of the characters that remain and return
+                let remaining_characters_slice = &english[start_of_in_between_words_index..];
+                pig_latin_string.extend_from_slice(remaining_characters_slice);
+                return;
             }
             //Copy the characters in-between words as-is
             let in_between_words_characters_slice = &english[start_of_in_between_words_index..global_index];
@@ -378,101 +685,51 @@ pub(crate) fn translate_with_style_lower_and_upper_suffixes (
         //Translates the current word
         {
             let word_start_index = global_index;
-            let first_letter = english[word_start_index];
-            global_index += 1;
-
-            if (global_index == english.len()) || (!english[global_index].is_ascii_alphabetic()) {//The word is only one letter long (special case)
-                //Push the letter and add the lowercase special suffix (even if the letter is uppercase)
-                pig_latin_string.push(first_letter);
-                pig_latin_string.extend_from_slice(special_case_suffix_lower);
-            } else if is_vowel(first_letter) {//The word is longer than a letter and starts with a vowel (special case)
-                //As a heuristic, we consider Y to be a vowel when it is not at the start of the word
-
-                //Get the slice containing the whole word
-                let slice_to_search_for_end = &english[global_index..];
-                let word_slice: &[u8];
-                if let Some(found_end_of_word_index) = slice_to_search_for_end.iter().position(|&x| !x.is_ascii_alphabetic()) {//We found a non-letter that ends the word
-                    global_index += found_end_of_word_index;
-                    word_slice = &english[word_start_index..global_index];
-                } else {//The string ended
-                    global_index = english.len();
-                    word_slice = slice_to_search_for_end;
-                }
 
-                //Translate the word and push it
-                pig_latin_string.extend_from_slice(word_slice);
-                if fast_is_ascii_uppercase(english[word_start_index + 1]) {//As a heuristic, we consider the word to be uppercase if the second letter is
-                    pig_latin_string.extend_from_slice(special_case_suffix_upper);
-                } else {//Word is entirely lowercase, or its first letter is uppercase only
-                    pig_latin_string.extend_from_slice(special_case_suffix_lower);
-                }
-            } else {//The word is longer than a letter and doesn't start with a vowel
-                //Find the first vowel; we assume the word actually has a vowel in it
-                let first_vowel_index: usize;
-                let slice_to_search_for_vowel = &english[global_index..];
-                if let Some(first_vowel_of_word_index) = slice_to_search_for_vowel.iter().position(|&x| { is_vowel(x) || is_y(x) }) {//As a heuristic, we consider Y to be a vowel when it is not at the start of the word
-                    global_index += first_vowel_of_word_index;
-                } else {//This string ended and we never found a vowel
-                    return;//Just give up
-                }
-                first_vowel_index = global_index;
-
-                //Find the end of the word
-                let word_end_index: usize;
-                let slice_to_search_for_end = &english[global_index..];
-                if let Some(end_of_word_index) = slice_to_search_for_end.iter().position(|&x| !x.is_ascii_alphabetic()) {//We found a non-letter that ends the word
-                    global_index += end_of_word_index;
-                } else {//The string ended
-                    global_index = english.len();
-                }
-                word_end_index = global_index;
-
-                //Translate the word
-                //TODO improve code reuse here
-                if fast_is_ascii_uppercase(first_letter) {//Check if the first letter is uppercase
-                    if fast_is_ascii_uppercase(english[word_start_index + 1]) {//As a heuristic, we consider the word to be uppercase if the second letter is
-                        //Push the vowel and all letters after it
-                        let vowel_to_end_slice = &english[first_vowel_index..word_end_index];
-                        pig_latin_string.extend_from_slice(vowel_to_end_slice);
-
-                        //Push the starting consonants
-                        let start_to_vowel_slice = &english[word_start_index..first_vowel_index];
-                        pig_latin_string.extend_from_slice(start_to_vowel_slice);
-
-                        //Push the normal suffix (uppercase)
-                        pig_latin_string.extend_from_slice(suffix_upper);
-                    } else {//Word starts with an uppercase letter, but is otherwise lowercase
-                        //Push the vowel, matching the starting case of the original word
-                        pig_latin_string.push(fast_to_ascii_uppercase(english[first_vowel_index]));
-
-                        //Push all letters after the vowel
-                        let after_vowel_slice = &english[(first_vowel_index + 1)..word_end_index];
-                        pig_latin_string.extend_from_slice(after_vowel_slice);
-
-                        //Push the first starting consonant, which should be lowercase now
-                        pig_latin_string.push(fast_to_ascii_lowercase(english[word_start_index]));
-
-                        //Push the remaining starting consonants
-                        let after_start_to_vowel_slice = &english[(word_start_index + 1)..first_vowel_index];
-                        pig_latin_string.extend_from_slice(after_start_to_vowel_slice);
-
-                        //Push the normal suffix
-                        pig_latin_string.extend_from_slice(suffix_lower);
-                    }
-                } else {//Word is entirely lowercase
-                    //Push the vowel and all letters after it
-                    let vowel_to_end_slice = &english[first_vowel_index..word_end_index];
-                    pig_latin_string.extend_from_slice(vowel_to_end_slice);
+            //Find the end of the word (we already know the first character is a letter)
+            let slice_to_search_for_end = &english[(global_index + 1)..];
+            if let Some(end_of_word_index) = find_non_letter(slice_to_search_for_end, classes) {//We found a non-letter that ends the word
+                global_index += end_of_word_index + 1;
+            } else {//The string ended
+                global_index = english.len();
+            }
 
-                    //Push the starting consonants
-                    let start_to_vowel_slice = &english[word_start_index..first_vowel_index];
-                    pig_latin_string.extend_from_slice(start_to_vowel_slice);
+            //RulesVersion::V0_5 reproduces anslatortray 0.5.0 exactly, including its bugs with words at the end of the text or without a vowel
+            if (rules.version.resolve() == RulesVersion::V0_5) && (global_index - word_start_index > 1) {//TODO annotate this branch as unlikely taken
+                if classes.is_vowel(english[word_start_index]) {
+                    if global_index == english.len() {
+                        //0.5.0 lost the first letter of a word starting with a vowel at the very end of the text
+                        pig_latin_string.extend_from_slice(&english[(word_start_index + 1)..]);
+                        if classes.is_uppercase(english[word_start_index + 1]) {
+                            pig_latin_string.extend_from_slice(special_case_suffix_upper);
+                        } else {
+                            pig_latin_string.extend_from_slice(special_case_suffix_lower);
+                        }
+                        return;
+                    }
+                } else if !english[(word_start_index + 1)..global_index].iter().any(|&x| classes.is_vowel_or_y(x)) {
+                    //0.5.0 kept looking for a vowel past the end of a word without one, and gave up on the rest of the text if there wasn't one
+                    if let Some(first_vowel_offset) = english[global_index..].iter().position(|&x| classes.is_vowel_or_y(x)) {
+                        global_index += first_vowel_offset;
+                    } else {
+                        return;
+                    }
 
-                    //Push the normal suffix (lowercase)
-                    pig_latin_string.extend_from_slice(suffix_lower);
+                    //The "word" then went on until the end of the word that vowel is in
+                    if let Some(end_of_word_offset) = find_non_letter(&english[global_index..], classes) {
+                        global_index += end_of_word_offset;
+                    } else {
+                        global_index = english.len();
+                    }
                 }
             }
 
+            translate_word_with_style_reuse_buffers (
+                &english[word_start_index..global_index], rules,
+                suffix_lower, special_case_suffix_lower, suffix_upper, special_case_suffix_upper,
+                pig_latin_string
+            );
+
             //Don't go on if we reached the end of the string during the word
             if global_index == english.len() {
                 return;
@@ -481,23 +738,17 @@ pub(crate) fn translate_with_style_lower_and_upper_suffixes (
             //At this point, global_index contains the index to the next character to check
         }
 
-        //Copies contraction suffixes, if present
-        if english[global_index] == b'\'' {//TODO if this is true we can also skip the regular inter-word loop on the next iteration
-            let mut start_of_contraction_suffix_index: usize = global_index;//Inclusive
-            global_index += 1;//We skip over the apostrophe for the loop below, but we still want to copy it in the end
-            loop {
-                if global_index == english.len() {
-                    //Copy all of the characters so far (all that remain) and return
-                    let remaining_characters_slice = &english[start_of_contraction_suffix_index..];
-                    pig_latin_string.extend_from_slice(remaining_characters_slice);
-                    return;
-                }
-
-                if !english[global_index].is_ascii_alphabetic() {//End of the contraction suffix
-                    break;
-                }
-
-                global_index += 1;
+        //Copies contraction suffixes, if present (they have to start with a letter, so a joiner directly after the apostrophe doesn't join anything)
+        if classes.is_apostrophe(english[global_index]) && (global_index + 1 < english.len()) && classes.is_letter(english[global_index + 1]) {//TODO if this is true we can also skip the regular inter-word loop on the next iteration
+            let start_of_contraction_suffix_index: usize = global_index;//Inclusive
+            global_index += 1;//We skip over the apostrophe for the search below, but we still want to copy it in the end
+            if let Some(end_of_contraction_suffix_offset) = find_non_letter(&english[global_index..], classes) {
+                global_index += end_of_contraction_suffix_offset;
+            } else {
+                //Copy all of the characters that remain and return
+                let remaining_characters_slice = &english[start_of_contraction_suffix_index..];
+                pig_latin_string.extend_from_slice(remaining_characters_slice);
+                return;
             }
             //Copy the contraction suffix as-is
             let contraction_suffix_slice = &english[start_of_contraction_suffix_index..global_index];
@@ -506,206 +757,171 @@ pub(crate) fn translate_with_style_lower_and_upper_suffixes (
     }
 }
 
-//Avoids the overhead of having to convert suffixes to uppercase for the standard translation functions at runtime
-pub(crate) fn translate_with_style_lower_and_upper_suffixes_old (
-    english: &[u8],
+//Translates a single word found by a tokenizer (which may contain anything, not just ASCII letters) using a style
+#[cfg(feature = "alloc")]
+pub(crate) fn translate_word_with(english_word: &[u8], style: &Style, pig_latin_string: &mut Vec::<u8>) {
+    translate_word_with_style_reuse_buffers (
+        english_word, style.word_rules(),
+        style.suffix_lower(), style.special_case_suffix_lower(), style.suffix_upper(), style.special_case_suffix_upper(),
+        pig_latin_string
+    );
+}
+
+//Translate a word (english_word must not be empty; anything other than an ASCII letter in it is treated as a consonant that is never uppercase)
+#[inline(always)]
+fn translate_word_with_style_reuse_buffers<O: Output> (
+    english_word: &[u8], rules: WordRules<'_>,//Assumes this word is not empty
     suffix_lower: &[u8], special_case_suffix_lower: &[u8], suffix_upper: &[u8], special_case_suffix_upper: &[u8],
-    pig_latin_string: &mut Vec::<u8>
+    buffer_to_append_to: &mut O
 ) {
-    if english.is_empty() {
-        return;
-    }
+    let analysis = analyze_word(english_word, rules);
+
+    match analysis.rule {
+        Rule::OneLetter => {
+            //Push the letter and add the lowercase special suffix (even if the letter is uppercase)
+            //TODO it may be better to chain these back to back in a single call so the vector gets a hint with how much it needs to resize for both at once
+            //See https://stackoverflow.com/questions/71785682/calling-extend-from-slice-multiple-times
+            buffer_to_append_to.extend_from_slice(english_word);
+            buffer_to_append_to.extend_from_slice(special_case_suffix_lower);
+        },
+        Rule::VowelStart => {
+            buffer_to_append_to.extend_from_slice(english_word);
+            if analysis.case == CaseClass::Upper {
+                buffer_to_append_to.extend_from_slice(special_case_suffix_upper);
+            } else {
+                buffer_to_append_to.extend_from_slice(special_case_suffix_lower);
+            }
+        },
+        Rule::NoVowel => {
+            //Just copy it as-is then
+            buffer_to_append_to.extend_from_slice(english_word);
+            if analysis.case == CaseClass::Upper {
+                buffer_to_append_to.extend_from_slice(suffix_upper);
+            } else {
+                buffer_to_append_to.extend_from_slice(suffix_lower);
+            }
+        },
+        Rule::ConsonantCluster => {
+            let index_of_first_vowel = analysis.onset_len;
 
-    //TODO merge the word and the generic text function into one function to allow for optimizations with certain things
-    //TODO do an SSE/AVX optimized version of this
-
-    //Flags used to remember if we're currently processing a word, contraction, contraction suffix or neither
-    //TODO can we avoid needing these flags and be more efficient?
-    let mut in_word: bool = false;
-    let mut in_contraction_suffix: bool = false;
-
-    //Indexes for improved performance (avoid copying characters to use as the english_word argument for translate_word_with_style_reuse_buffers)
-    //However, this assumes each character is one byte, so this only works with ASCII strings
-    let mut slice_start_index: usize = 0;//Inclusive
-    let mut slice_end_index: usize = 0;//Exclusive
-
-    for character in english.iter() {
-        if in_word {
-            if in_contraction_suffix {
-                if character.is_ascii_alphabetic() {
-                    //We never translate the contraction suffix of a word, so just copy remaining letters as-is
-                } else {
-                    //The contraction ended, and so too does the word
-                    //We still want to copy the non-letter to the output though
-                    in_contraction_suffix = false;
-                    in_word = false;
-                }
+            //Push the first vowel to the new pig latin string, matching the starting case of the original word if needed
+            if analysis.capitalize_rime {
+                buffer_to_append_to.push(fast_to_ascii_uppercase(english_word[index_of_first_vowel]));
+            } else {
+                buffer_to_append_to.push(english_word[index_of_first_vowel]);
+            }
+
+            //Copy the remaining letters in the word after the vowel
+            buffer_to_append_to.extend_from_slice(&english_word[(index_of_first_vowel + 1)..]);
 
-                pig_latin_string.push(*character);//Copy the character
-                slice_start_index += 1;//Keep the slice start index up to speed for later use
+            //If the first letter (a consonant) was capitalized, it no longer needs to be (since the vowel above is now at the start and capitalized)
+            if analysis.lowercase_onset {
+                buffer_to_append_to.push(fast_to_ascii_lowercase(english_word[0]));
             } else {
-                if character.is_ascii_alphabetic() {
-                    //This character is part of the word, so increment the slice_end_index to include it in the slice
-                    slice_end_index += 1;
-                } else {
-                    //The word or first part of the contraction ended, so translate the word we've identified up until this point!
-                    let word_slice: &[u8] = &english[slice_start_index..slice_end_index];
-                    translate_word_with_style_reuse_buffers (
-                        word_slice,
-                        suffix_lower, special_case_suffix_lower, suffix_upper, special_case_suffix_upper,
-                        pig_latin_string
-                    );
-
-                    //Bring the slice_start_index to the end since we've finished the word and need it ready for the next one
-                    slice_start_index = slice_end_index + 1;
-
-                    //Append the symbol/whitespace we just got after the translated word
-                    pig_latin_string.push(*character);
-
-                    //If the symbol/whitespace we just got is an apostrophe, then this is a contraction suffix
-                    if *character == b'\'' {
-                        in_contraction_suffix = true;
-                    } else {
-                        in_word = false;//This wasn't a contraction, so we're done with the word
-                    }
-                }
+                buffer_to_append_to.push(english_word[0]);
             }
-        } else {
-            if character.is_ascii_alphabetic() {
-                //If we see a letter, we are in a word, so set the slice_end_index to the character after the slice_start_index
-                in_word = true;
-                slice_end_index = slice_start_index + 1;
+
+            //Copy the remaining starting consonants
+            buffer_to_append_to.extend_from_slice(&english_word[1..index_of_first_vowel]);
+
+            if analysis.case == CaseClass::Upper {//TODO annotate this branch as unlikely taken
+                buffer_to_append_to.extend_from_slice(suffix_upper);
             } else {
-                //Otherwise copy symbols and whitespace as-is
-                pig_latin_string.push(*character);
-                slice_start_index += 1;
+                buffer_to_append_to.extend_from_slice(suffix_lower);
             }
-        }
-    }
-    //If we ended on a word (but not on a contraction suffix), we translate it and push it to the end of the string
-    if in_word && !in_contraction_suffix {
-        let word_slice: &[u8] = &english[slice_start_index..slice_end_index];
-        translate_word_with_style_reuse_buffers (
-            word_slice,
-            suffix_lower, special_case_suffix_lower, suffix_upper, special_case_suffix_upper,
-            pig_latin_string
-        );
+        },
     }
 }
 
-//Translate a word (english_word MUST ONLY CONTAIN ASCII LETTERS, not numbers/symbols/etc or anything UTF-8)
-#[inline(always)]//Only used by the one function in this module, so this makes sense
-fn translate_word_with_style_reuse_buffers (
-    english_word: &[u8],//Assumes this word is not empty
-    suffix_lower: &[u8], special_case_suffix_lower: &[u8], suffix_upper: &[u8], special_case_suffix_upper: &[u8],
-    buffer_to_append_to: &mut Vec<u8>
-) {
+//Decides how a word will be translated (english_word must not be empty; anything other than an ASCII letter in it is treated as a consonant that is never uppercase)
+#[inline(always)]
+pub(crate) fn analyze_word(english_word: &[u8], rules: WordRules<'_>) -> WordAnalysis {
     //Assume the word is at least 1 letter
     debug_assert!(english_word.len() != 0);
+    #[cfg(not(feature = "safe"))]
     if english_word.len() == 0 {
         unsafe {
-            std::hint::unreachable_unchecked();
+            core::hint::unreachable_unchecked();
         }
     }
 
+    let classes = rules.classes;
+    let first_letter = english_word[0];
+
     //Special case for 1-letter words
     if english_word.len() == 1 {//TODO annotate this branch as unlikely taken
-        //TODO it may be better to chain these back to back in a single call so the vector gets a hint with how much it needs to resize for both at once
-        //See https://stackoverflow.com/questions/71785682/calling-extend-from-slice-multiple-times
-        buffer_to_append_to.extend_from_slice(english_word);
-        buffer_to_append_to.extend_from_slice(special_case_suffix_lower);
-        return;
+        let case = if classes.is_uppercase(first_letter) { CaseClass::Title } else { CaseClass::Lower };
+        return WordAnalysis { rule: Rule::OneLetter, case, onset_len: 0, capitalize_rime: false, lowercase_onset: false };
     }
 
-    //Check if the word is uppercase
-    let word_uppercase = word_is_uppercase(english_word);
+    let case = match rules.version.resolve() {
+        RulesVersion::V0_4 => {
+            //Heuristic: If the last letter of the word is uppercase, likely the whole word is uppercase
+            if word_is_uppercase(english_word, classes) {
+                CaseClass::Upper
+            } else if classes.is_uppercase(first_letter) {
+                CaseClass::Title
+            } else {
+                CaseClass::Lower
+            }
+        },
+        _ => {
+            //Heuristic: If the second letter of the word is uppercase, likely the whole word is uppercase
+            //(but a word starting with a lowercase consonant is always treated as lowercase)
+            let second_letter_uppercase = classes.is_uppercase(english_word[1]);
+            if classes.is_uppercase(first_letter) {
+                if second_letter_uppercase { CaseClass::Upper } else { CaseClass::Title }
+            } else if second_letter_uppercase && classes.is_vowel(first_letter) {
+                CaseClass::Upper
+            } else {
+                CaseClass::Lower
+            }
+        },
+    };
 
     //As a herustic, we consider Y to be a vowel when it is not at the start of the word
-    if is_vowel(english_word[0]) {//Not including y//TODO annotate this branch as unlikely taken
-        buffer_to_append_to.extend_from_slice(english_word);
-        if word_uppercase {
-            buffer_to_append_to.extend_from_slice(special_case_suffix_upper);
-        } else {
-            buffer_to_append_to.extend_from_slice(special_case_suffix_lower);
-        }
-        return;
-    }
-
-    //Find the index of the first vowel, skipping index 1 since that was handled above
-    let mut index_of_first_vowel: usize = 1;
-    while index_of_first_vowel < english_word.len() {
-        let character: u8 = english_word[index_of_first_vowel];
-        if is_vowel(character) || is_y(character) {//As a herustic, we consider Y to be a vowel when it is not at the start of the word
-            break;
-        }
-        index_of_first_vowel += 1;
-    }
-
-    //Now that we know where the first vowel is and if the word is uppercase, we can construct the pig-latin word
-    if index_of_first_vowel < english_word.len() {//We found a vowel//TODO mark this branch as likely taken
-        //Push the first vowel to the new pig latin string. If the first letter was capitalized originally, match the case
-        if fast_is_ascii_uppercase(english_word[0]) {
-            buffer_to_append_to.push(fast_to_ascii_uppercase(english_word[index_of_first_vowel]));
-        } else {
-            buffer_to_append_to.push(english_word[index_of_first_vowel]);
-        }
-
-        //Copy the remaining letters in the word after the vowel
-        buffer_to_append_to.extend_from_slice(&english_word[(index_of_first_vowel + 1)..]);
-
-        //If the first letter (a consonant) was uppercase, it no longer needs to be (since the vowel above is now at the start and capitalized)
-        //Unless, of course, the whole word is uppercase, in which case it should be left alone
-        buffer_to_append_to.push(if word_uppercase { english_word[0] } else { fast_to_ascii_lowercase(english_word[0]) });
-
-        //Copy the remaining starting consonants
-        buffer_to_append_to.extend_from_slice(&english_word[1..index_of_first_vowel]);
-    } else {//This word dosn't have a vowel
-        //Just copy it as-is then
-        buffer_to_append_to.extend_from_slice(english_word);
-    }
-
-    //Add the regular suffixes
-    if word_uppercase {//TODO annotate this branch as unlikely taken
-        buffer_to_append_to.extend_from_slice(suffix_upper);
-    } else {
-        buffer_to_append_to.extend_from_slice(suffix_lower);
-    }
-}
-
-//Returns whether a letter is a vowel or not.
-#[inline(always)]//Only used by the one function in this module, so this makes sense
-fn is_vowel(letter: u8) -> bool {
-    match letter {
-        b'a' | b'e' | b'i' | b'o' | b'u' | b'A' | b'E' | b'I' | b'O' | b'U' => { return true; }
-        _ => { return false; }
+    if classes.is_vowel(first_letter) {//Not including y//TODO annotate this branch as unlikely taken
+        return WordAnalysis { rule: Rule::VowelStart, case, onset_len: 0, capitalize_rime: false, lowercase_onset: false };
     }
-}
 
-//Returns whether a letter is y or not.
-#[inline(always)]//Only used by the one function in this module, so this makes sense
-fn is_y(letter: u8) -> bool {
-    return (letter == b'y') || (letter == b'Y');
+    //Find the index of the first vowel, skipping index 0 since that was handled above
+    let onset_len = match english_word[1..].iter().position(|&x| classes.is_vowel_or_y(x)) {//As a herustic, we consider Y to be a vowel when it is not at the start of the word
+        Some(index_of_first_vowel) => index_of_first_vowel + 1,
+        None => {//This word doesn't have a vowel
+            //Heuristic: Without a vowel there is nothing to rotate, so the word is uppercase if its second letter is, even if its first letter isn't (ex. "tCH")
+            let case = match rules.version.resolve() {
+                RulesVersion::V0_4 => case,
+                _ => if classes.is_uppercase(english_word[1]) { CaseClass::Upper } else { case },
+            };
+            return WordAnalysis { rule: Rule::NoVowel, case, onset_len: english_word.len(), capitalize_rime: false, lowercase_onset: false };
+        },
+    };
+
+    let (capitalize_rime, lowercase_onset) = match rules.version.resolve() {
+        //The vowel matches the case of the first letter, which is lowercased unless the whole word is uppercase
+        //(lowercasing is skipped if it isn't uppercase to begin with, so non-letters from custom tokenizers are left alone)
+        RulesVersion::V0_4 => (classes.is_uppercase(first_letter), (case != CaseClass::Upper) && classes.is_uppercase(first_letter)),
+        //Only capitalized words have their case moved from the first letter to the vowel
+        _ => (case == CaseClass::Title, case == CaseClass::Title),
+    };
+
+    return WordAnalysis { rule: Rule::ConsonantCluster, case, onset_len, capitalize_rime, lowercase_onset };
 }
 
 //Returns whether an entire word is upper case or not.
 #[inline(always)]//Only used by the one function in this module, so this makes sense
-fn word_is_uppercase(english_word: &[u8]) -> bool {
+fn word_is_uppercase(english_word: &[u8], classes: &ByteClasses) -> bool {
     //Asume length is non-zero
     debug_assert!(english_word.len() != 0);
+    #[cfg(not(feature = "safe"))]
     if english_word.len() == 0 {
         unsafe {
-            std::hint::unreachable_unchecked();
+            core::hint::unreachable_unchecked();
         }
     }
 
     //Heuristic: If the last letter of the word is uppercase, likely the whole word is uppercase
-    return fast_is_ascii_uppercase(english_word[english_word.len() - 1]);
-}
-
-//NOTE the result is undefined if the character is not a letter
-#[inline(always)]//Only used by the one function in this module, so this makes sense
-fn fast_is_ascii_uppercase(letter: u8) -> bool {
-    return letter <= b'Z';
+    return classes.is_uppercase(english_word[english_word.len() - 1]);
 }
 
 //NOTE the result is undefined if the character is not a letter
@@ -734,8 +950,69 @@ mod tests {
 
     //NOTE: We don't test byte_string::translate_with_style and other similar functions in here directly since we test them through string.rs
 
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_translated_len_and_translate_to_slice() {
+        let english: &[u8] = "Let's try some edge cases: ISn't THIS COOL? naïve mcDONALD's, bcd 123 Hyphens-are-difficult-aren't-they?' A".as_bytes();
+
+        for style in [Style::WAY, Style::FERB, Style::new("äy", "wäy"), Style::HAY.with_rules(RulesVersion::V0_4), Style::YAY.with_rules(RulesVersion::V0_5)] {
+            let mut expected = Vec::<u8>::new();
+            translate_with(english, &style, &mut expected);
+            assert_eq!(translated_len(english, &style), expected.len());
+
+            let mut buffer = vec![0u8; expected.len()];
+            assert_eq!(translate_to_slice(english, &style, &mut buffer), Ok(expected.len()));
+            assert_eq!(buffer, expected);
+
+            //One byte short (and way too short) must fail without panicking
+            assert_eq!(translate_to_slice(english, &style, &mut buffer[1..]), Err(BufferTooSmall { required: expected.len() }));
+            assert_eq!(translate_to_slice(english, &style, &mut []), Err(BufferTooSmall { required: expected.len() }));
+        }
+
+        assert_eq!(translated_len(b"", &Style::WAY), 0);
+        assert_eq!(translate_to_slice(b"", &Style::WAY, &mut []), Ok(0));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_translate_yay_uppercase() {
+        //anslatortray 0.5.0 appended "WAY" here (unlike crate::translate_yay())
+        let mut buffer = Vec::<u8>::new();
+        translate_yay(b"ISN'T IT?", &mut buffer);
+        assert_eq!(&buffer, b"ISNYAY'T ITYAY?");
+    }
+
     //TODO test uppercase words
     #[test]
+    #[cfg(feature = "alloc")]
+    fn test_try_translate() {
+        const ENGLISH: &str = "Let's try some edge cases: ISn't THIS COOL? naïve mcDONALD's, bcd 123 🐷";
+
+        let mut expected = Vec::new();
+        translate_with(ENGLISH.as_bytes(), &Style::FERB, &mut expected);
+        let mut pig_latin = Vec::new();
+        assert_eq!(try_translate_with(ENGLISH.as_bytes(), &Style::FERB, &mut pig_latin), Ok(()));
+        assert_eq!(pig_latin, expected);
+
+        //Break the input at every byte of every non-ASCII character
+        for (index, _) in ENGLISH.bytes().enumerate().filter(|(_, byte)| !byte.is_ascii()) {
+            let mut english = ENGLISH.as_bytes().to_vec();
+            english[index] = 0xFF;
+            let expected_error = core::str::from_utf8(&english).unwrap_err();
+
+            let mut pig_latin = b"> ".to_vec();
+            let error = try_translate(&english, &mut pig_latin).unwrap_err();
+            assert_eq!((error.valid_up_to, error.error_len), (expected_error.valid_up_to(), expected_error.error_len()));
+            assert_eq!(pig_latin, b"> ");
+        }
+
+        let mut pig_latin = Vec::new();
+        assert_eq!(try_translate(b"", &mut pig_latin), Ok(()));
+        assert_eq!(try_translate(b"\xF0\x9F\x90", &mut pig_latin), Err(InvalidUtf8 { valid_up_to: 0, error_len: None }));
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
     fn test_translate_word_with_style() {
         let suffix_special_case_suffix_pairs = [
             ("ay", "way"), ("ay", "yay"), ("ay", "hay"), ("erb", "ferb"), ("ancy", "fancy"), ("orange", "porange"), ("anana", "banana"), ("atin", "latin"), ("ust", "rust")
@@ -773,6 +1050,29 @@ mod tests {
         }
     }
 
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_translate_with_rules_versions_no_vowels() {
+        const ENGLISH: &[u8] = b"tCH sT, bcd TSK Hmm";
+
+        for (rules, expected) in [
+            (RulesVersion::V0_4, b"tCHAY sTAY, bcday TSKAY Hmmay".as_slice()),
+            (RulesVersion::V0_5, b"".as_slice()),//anslatortray 0.5.0 gave up on the rest of the text if there wasn't a vowel left in it
+            (RulesVersion::V0_6, b"tCHAY sTAY, bcday TSKAY Hmmay".as_slice()),
+        ] {
+            let mut buffer = Vec::<u8>::new();
+            translate_with(ENGLISH, &Style::WAY.with_rules(rules), &mut buffer);
+            assert_eq!(buffer, expected, "{:?}", rules);
+        }
+
+        //Translating a word at a time gives V0_5 the same output as V0_6
+        let mut buffer = Vec::<u8>::new();
+        translate_word_with(b"tCH", &Style::WAY.with_rules(RulesVersion::V0_5), &mut buffer);
+        translate_word_with(b"sT", &Style::WAY.with_rules(RulesVersion::V0_5), &mut buffer);
+        assert_eq!(buffer, b"tCHAYsTAY");
+    }
+
+    #[cfg(feature = "alloc")]
     fn translate_word_with_style(english_word: &str, suffix_lower: &str, special_case_suffix_lower: &str) -> String {
         let mut suffix_upper = String::new();
         for letter in suffix_lower.chars() {
@@ -785,63 +1085,26 @@ mod tests {
 
         let mut pig_latin_word = Vec::<u8>::new();
         translate_word_with_style_reuse_buffers (
-            english_word.as_bytes(),
-            suffix_lower.as_bytes(), special_case_suffix_lower.as_bytes(), &suffix_upper.as_bytes(), &special_case_suffix_upper.as_bytes(),
+            english_word.as_bytes(), WordRules { version: RulesVersion::V0_4, classes: &ByteClasses::ENGLISH },
+            suffix_lower.as_bytes(), special_case_suffix_lower.as_bytes(), suffix_upper.as_bytes(), special_case_suffix_upper.as_bytes(),
             &mut pig_latin_word
         );
-        return std::str::from_utf8(pig_latin_word.as_slice()).unwrap().to_string();
-    }
-
-    #[test]
-    fn test_is_vowel() {
-        for letter in b"aeiouAEIOU".iter() {
-            assert!(is_vowel(*letter));
-        }
-
-        for letter in b"bcdfghjklmnpqrstvwxyzBCDFGHJKLMNPQRSTVWXYZ".iter() {
-            assert!(!is_vowel(*letter));
-        }
-
-        for not_letter in b" !@#$%^&*()_+={}|\":>?~`\\][';/.,\t\n".iter() {
-            assert!(!is_vowel(*not_letter));
-        }
-    }
-
-    #[test]
-    fn test_is_y() {
-        for letter in b"yY".iter() {
-            assert!(is_y(*letter));
-        }
-
-        for letter in b"abcdefghijklmnopqrstuvwxzABCDEFGHIJKLMNOPQRSTUVWXZ".iter() {
-            assert!(!is_y(*letter));
-        }
-
-        for not_letter in b" !@#$%^&*()_+={}|\":>?~`\\][';/.,\t\n".iter() {
-            assert!(!is_y(*not_letter));
-        }
+        return core::str::from_utf8(pig_latin_word.as_slice()).unwrap().to_string();
     }
 
     #[test]
     fn test_word_is_uppercase() {
-        assert!(word_is_uppercase(b"HELLO"));
-        assert!(word_is_uppercase(b"WORLD"));
+        assert!(word_is_uppercase(b"HELLO", &ByteClasses::ENGLISH));
+        assert!(word_is_uppercase(b"WORLD", &ByteClasses::ENGLISH));
 
-        assert!(word_is_uppercase(b"I"));
-        assert!(!word_is_uppercase(b"would"));
-        assert!(!word_is_uppercase(b"like"));
-        assert!(!word_is_uppercase(b"a"));
-        assert!(!word_is_uppercase(b"pizza"));
+        assert!(word_is_uppercase(b"I", &ByteClasses::ENGLISH));
+        assert!(!word_is_uppercase(b"would", &ByteClasses::ENGLISH));
+        assert!(!word_is_uppercase(b"like", &ByteClasses::ENGLISH));
+        assert!(!word_is_uppercase(b"a", &ByteClasses::ENGLISH));
+        assert!(!word_is_uppercase(b"pizza", &ByteClasses::ENGLISH));
 
-        assert!(!word_is_uppercase(b"Sussus"));
-        assert!(!word_is_uppercase(b"Amogus"));
-    }
-
-    #[test]
-    fn test_fast_is_ascii_uppercase() {
-        for letter in b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ".iter() {
-            assert_eq!(fast_is_ascii_uppercase(*letter), letter.is_ascii_uppercase());
-        }
+        assert!(!word_is_uppercase(b"Sussus", &ByteClasses::ENGLISH));
+        assert!(!word_is_uppercase(b"Amogus", &ByteClasses::ENGLISH));
     }
 
     /*#[test]
@@ -886,7 +1149,7 @@ mod benches {
             let word = test::black_box(b"translator");
 
             translate_word_with_style_reuse_buffers (
-                word,
+                word, WordRules::LATEST,
                 b"ay", b"way", b"AY", b"WAY",
                 &mut pig_latin_word
             );
@@ -894,7 +1157,7 @@ mod benches {
             pig_latin_word.truncate(0);
         });
 
-        eprintln!("{}", std::str::from_utf8(pig_latin_word.as_slice()).unwrap());//To avoid optimizing things out
+        eprintln!("{}", core::str::from_utf8(pig_latin_word.as_slice()).unwrap());//To avoid optimizing things out
     }
 
     #[bench]
@@ -905,7 +1168,7 @@ mod benches {
             let word = test::black_box(b"translator");
 
             translate_word_with_style_reuse_buffers (
-                word,
+                word, WordRules::LATEST,
                 b"ay", b"yay", b"AY", b"YAY",
                 &mut pig_latin_word
             );
@@ -913,7 +1176,7 @@ mod benches {
             pig_latin_word.truncate(0);
         });
 
-        eprintln!("{}", std::str::from_utf8(pig_latin_word.as_slice()).unwrap());//To avoid optimizing things out
+        eprintln!("{}", core::str::from_utf8(pig_latin_word.as_slice()).unwrap());//To avoid optimizing things out
     }
 
     #[bench]
@@ -924,7 +1187,7 @@ mod benches {
             let word = test::black_box(b"translator");
 
             translate_word_with_style_reuse_buffers (
-                word,
+                word, WordRules::LATEST,
                 b"ay", b"hay", b"AY", b"HAY",
                 &mut pig_latin_word
             );
@@ -932,7 +1195,7 @@ mod benches {
             pig_latin_word.truncate(0);
         });
 
-        eprintln!("{}", std::str::from_utf8(pig_latin_word.as_slice()).unwrap());//To avoid optimizing things out
+        eprintln!("{}", core::str::from_utf8(pig_latin_word.as_slice()).unwrap());//To avoid optimizing things out
     }
 
     #[bench]
@@ -943,7 +1206,7 @@ mod benches {
             let word = test::black_box(b"translator");
 
             translate_word_with_style_reuse_buffers (
-                word,
+                word, WordRules::LATEST,
                 b"erb", b"ferb", b"ERB", b"FERB",
                 &mut pig_latin_word
             );
@@ -951,7 +1214,7 @@ mod benches {
             pig_latin_word.truncate(0);
         });
 
-        eprintln!("{}", std::str::from_utf8(pig_latin_word.as_slice()).unwrap());//To avoid optimizing things out
+        eprintln!("{}", core::str::from_utf8(pig_latin_word.as_slice()).unwrap());//To avoid optimizing things out
     }
 
     #[bench]
@@ -1057,4 +1320,23 @@ mod benches {
             pig_latin_word.truncate(0);
         });
     }
+
+    #[bench]
+    fn way_long_separators(b: &mut Bencher) {
+        //Lots of indentation, numbers and punctuation between words, which the vectorized scanning skips over quickly
+        let mut english = Vec::<u8>::new();
+        for _ in 0..16 {
+            english.extend_from_slice(b"                                                                1234567890, 1234567890; (0xDEADBEEF)\n");
+            english.extend_from_slice(b"Supercalifragilisticexpialidocious pneumonoultramicroscopicsilicovolcanoconiosis\n");
+        }
+        let mut pig_latin_word = Vec::<u8>::with_capacity(english.len() * 2);
+
+        b.iter(|| {
+            let word = test::black_box(english.as_slice());
+
+            translate_way(word, &mut pig_latin_word);
+
+            pig_latin_word.truncate(0);
+        });
+    }
 }
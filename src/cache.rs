@@ -0,0 +1,333 @@
+/* cache.rs
+ * By: John Jekel
+ * Copyright (C) 2022 John Jekel
+ * See the LICENSE file at the root of the project for licensing info.
+ *
+ * A translator that remembers the translations of words it has already seen
+ *
+*/
+
+/* Imports */
+
+use std::collections::HashMap;
+use std::hash::{BuildHasherDefault, Hasher};
+
+use crate::byte_string::translate_with as translate_byte_string_with;
+use crate::spans::{Spans, SpanKind};
+use crate::style::Style;
+use crate::utf8::translated_string;
+
+/* Constants */
+
+///The number of distinct words a [`CachingTranslator`] remembers by default.
+pub const DEFAULT_CACHE_CAPACITY: usize = 4096;
+
+/* Types */
+
+///Translates text like [`crate::translate_with()`], remembering the translation of every word it sees (up to a fixed number of words)!
+///
+///Natural language repeats a small vocabulary heavily, so in long texts most words can be copied straight out of the cache
+///instead of being analyzed again. Words are cached by their exact bytes, so "Hello" and "hello" are separate entries.
+///Once the cache is full, each new word replaces one that hasn't been used recently (CLOCK eviction),
+///so the cache keeps up with the vocabulary of a text as it changes, and words that keep appearing are rarely evicted.
+///
+///Translating a single word is already cheap, so a hash lookup is not always faster (on ordinary English text it is usually a little slower,
+///as the `cache` benches show): use [`CachingTranslator::stats()`] (and `anslatortray --benchmark-file`) to check whether the cache actually helps for a given corpus.
+///
+///Requires the `std` feature.
+///
+///# Examples
+///
+///```
+///use anslatortray::{CachingTranslator, Style};
+///
+///let mut translator = CachingTranslator::new(Style::WAY, 1024);
+///
+///assert_eq!(translator.translate("Hello world!"), "Ellohay orldway!");
+///assert_eq!(translator.translate("Hello again, world!"), "Ellohay againway, orldway!");
+///
+///let stats = translator.stats();
+///assert_eq!((stats.hits, stats.misses), (2, 3));
+///assert_eq!(translator.len(), 3);
+///```
+#[derive(Clone, Debug)]
+pub struct CachingTranslator {
+    style: Style,
+    capacity: usize,
+    index: HashMap<Box<[u8]>, usize, BuildHasherDefault<WordHasher>>,//Where each cached word is in entries
+    entries: Vec<CacheEntry>,
+    clock_hand: usize,
+    stats: CacheStats,
+    pig_latin_word: Vec<u8>,
+}
+
+//A cached translation, in the ring the clock hand sweeps over
+#[derive(Clone, Debug)]
+struct CacheEntry {
+    english: Box<[u8]>,
+    pig_latin: Box<[u8]>,
+    referenced: bool,//Whether the word was used since the clock hand last passed it
+}
+
+///How often a [`CachingTranslator`] found words in its cache.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct CacheStats {
+    ///The number of words whose translation was copied out of the cache
+    pub hits: u64,
+    ///The number of words that had to be translated
+    pub misses: u64,
+}
+
+//Hashes eight bytes at a time (like rustc's FxHash): words are short, so this is much cheaper than the default SipHash (and we don't need DoS resistance for a cache we cap anyways)
+#[derive(Clone, Copy, Debug)]
+struct WordHasher(u64);
+
+/* Associated Functions and Methods */
+
+impl CachingTranslator {
+    ///Creates a translator using the given [`Style`] that remembers up to `capacity` distinct words.
+    pub fn new(style: Style, capacity: usize) -> CachingTranslator {
+        return CachingTranslator {
+            style,
+            capacity,
+            index: HashMap::default(),
+            entries: Vec::new(),
+            clock_hand: 0,
+            stats: CacheStats::default(),
+            pig_latin_word: Vec::new(),
+        };
+    }
+
+    ///Returns the [`Style`] used for translation.
+    pub fn style(&self) -> &Style {
+        return &self.style;
+    }
+
+    ///Returns the maximum number of distinct words the cache remembers.
+    pub fn capacity(&self) -> usize {
+        return self.capacity;
+    }
+
+    ///Returns the number of distinct words currently in the cache.
+    pub fn len(&self) -> usize {
+        return self.entries.len();
+    }
+
+    ///Returns true if no words are in the cache.
+    pub fn is_empty(&self) -> bool {
+        return self.entries.is_empty();
+    }
+
+    ///Returns the hit/miss statistics since the translator was created (or since [`CachingTranslator::reset_stats()`]).
+    pub fn stats(&self) -> CacheStats {
+        return self.stats;
+    }
+
+    ///Resets the hit/miss statistics to zero, keeping the cached words.
+    pub fn reset_stats(&mut self) {
+        self.stats = CacheStats::default();
+    }
+
+    ///Forgets every cached word, keeping the statistics.
+    pub fn clear(&mut self) {
+        self.index.clear();
+        self.entries.clear();
+        self.clock_hand = 0;
+    }
+
+    ///Translates a multi-word string (including punctuation), using and filling the cache.
+    pub fn translate(&mut self, english: &str) -> String {
+        let mut pig_latin_string_bytes = Vec::<u8>::with_capacity(english.len() * 2);//Plenty of headroom in case the words are very small or the suffixes are long
+        self.translate_bytes(english.as_bytes(), &mut pig_latin_string_bytes);
+
+        return translated_string(pig_latin_string_bytes);
+    }
+
+    ///Translates a multi-word byte-string (including punctuation) like [`crate::byte_string::translate_with()`], using and filling the cache.
+    ///
+    ///Note: The resulting translation is appended to the provided buffer, so one may wish to ensure it is cleared before each use or not depending on the application.
+    pub fn translate_bytes(&mut self, english: &[u8], pig_latin_string: &mut Vec<u8>) {
+        for span in Spans::new(english, self.style.classes()) {
+            let text = &english[span.range];
+            if span.kind != SpanKind::Word {
+                pig_latin_string.extend_from_slice(text);
+                continue;
+            }
+
+            if let Some(&entry_index) = self.index.get(text) {
+                self.stats.hits += 1;
+                let entry = &mut self.entries[entry_index];
+                entry.referenced = true;
+                pig_latin_string.extend_from_slice(&entry.pig_latin);
+                continue;
+            }
+
+            self.stats.misses += 1;
+            self.pig_latin_word.truncate(0);
+            translate_byte_string_with(text, &self.style, &mut self.pig_latin_word);
+            pig_latin_string.extend_from_slice(&self.pig_latin_word);
+
+            self.remember(text);
+        }
+    }
+
+    //Caches the translation of a word that was just put in pig_latin_word, evicting one that hasn't been used recently if the cache is full
+    fn remember(&mut self, english_word: &[u8]) {
+        let entry = CacheEntry {
+            english: english_word.into(),
+            pig_latin: self.pig_latin_word.as_slice().into(),
+            referenced: false,//Words seen only once are the first to go
+        };
+
+        if self.entries.len() < self.capacity {
+            self.index.insert(english_word.into(), self.entries.len());
+            self.entries.push(entry);
+            return;
+        } else if self.capacity == 0 {
+            return;
+        }
+
+        //Sweep the clock hand, giving each recently-used word a second chance, until it finds one that hasn't been used since it last passed
+        while self.entries[self.clock_hand].referenced {
+            self.entries[self.clock_hand].referenced = false;
+            self.clock_hand = (self.clock_hand + 1) % self.entries.len();
+        }
+
+        let evicted = core::mem::replace(&mut self.entries[self.clock_hand], entry);
+        self.index.remove(&evicted.english);
+        self.index.insert(english_word.into(), self.clock_hand);
+        self.clock_hand = (self.clock_hand + 1) % self.entries.len();
+    }
+}
+
+impl CacheStats {
+    ///Returns the total number of words looked up.
+    pub fn lookups(&self) -> u64 {
+        return self.hits + self.misses;
+    }
+
+    ///Returns the fraction of lookups that were hits (0.0 if there were none).
+    pub fn hit_rate(&self) -> f64 {
+        if self.lookups() == 0 {
+            return 0.0;
+        }
+        return self.hits as f64 / self.lookups() as f64;
+    }
+}
+
+impl Default for WordHasher {
+    fn default() -> WordHasher {
+        return WordHasher(0xCBF29CE484222325);
+    }
+}
+
+impl WordHasher {
+    fn add(&mut self, word: u64) {
+        self.0 = (self.0.rotate_left(5) ^ word).wrapping_mul(0x517CC1B727220A95);
+    }
+}
+
+impl Hasher for WordHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        let mut chunks = bytes.chunks_exact(8);
+        for chunk in &mut chunks {
+            self.add(u64::from_le_bytes(chunk.try_into().unwrap()));
+        }
+        let mut rest = [0u8; 8];
+        rest[..chunks.remainder().len()].copy_from_slice(chunks.remainder());
+        self.add(u64::from_le_bytes(rest));
+    }
+
+    fn finish(&self) -> u64 {
+        return self.0;
+    }
+}
+
+/* Tests */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::style::RulesVersion;
+
+    const ENGLISH: &str = "Let's try some edge cases: ISn't THIS COOL? naïve mcDONALD's, bcd 123 Hyphens-are-difficult-aren't-they?' Let's try THIS again";
+
+    #[test]
+    fn test_caching_translator_matches_translate_with() {
+        static HYPHENATED: crate::ByteClasses = crate::ByteClasses::ENGLISH.with_joiners(b"-");
+        for style in [Style::WAY, Style::FERB, Style::new("äy", "wäy"), Style::HAY.with_rules(RulesVersion::V0_4), Style::WAY.with_classes(&HYPHENATED)] {
+            let expected = crate::translate_with(ENGLISH, &style);
+
+            for capacity in [0, 1, 5, DEFAULT_CACHE_CAPACITY] {
+                let mut translator = CachingTranslator::new(style, capacity);
+                assert_eq!(translator.translate(ENGLISH), expected);
+                assert_eq!(translator.translate(ENGLISH), expected);
+                assert!(translator.len() <= capacity);
+            }
+        }
+    }
+
+    #[test]
+    fn test_caching_translator_stats() {
+        let mut translator = CachingTranslator::new(Style::WAY, 2);
+        assert_eq!(translator.stats().hit_rate(), 0.0);
+
+        //"the" and "cat" fill the cache, so "hat" evicts "cat" (which wasn't used again), then "cat" evicts "hat"
+        assert_eq!(translator.translate("the cat the hat the cat the"), "ethay atcay ethay athay ethay atcay ethay");
+        assert_eq!(translator.stats(), CacheStats { hits: 3, misses: 4 });
+        assert_eq!(translator.stats().lookups(), 7);
+        assert_eq!(translator.len(), 2);
+
+        //"the" keeps being used, so it survives a run of new words
+        translator.reset_stats();
+        translator.translate("the a the b the c the");
+        assert_eq!(translator.stats(), CacheStats { hits: 4, misses: 3 });
+
+        //Case matters
+        translator.reset_stats();
+        translator.translate("The");
+        assert_eq!(translator.stats(), CacheStats { hits: 0, misses: 1 });
+
+        translator.clear();
+        assert!(translator.is_empty());
+        translator.translate("the the");
+        assert_eq!(translator.stats(), CacheStats { hits: 1, misses: 2 });
+    }
+}
+
+/* Benches */
+
+#[cfg_attr(feature = "nightly-features-benches", cfg(test))]
+#[cfg(feature = "nightly-features-benches")]
+mod benches {
+    extern crate test;
+    use test::Bencher;
+    use super::*;
+
+    const LOREM_IPSUM: &[u8] = b"Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua. Ut enim ad minim veniam, quis nostrud exercitation ullamco laboris nisi ut aliquip ex ea commodo consequat. Duis aute irure dolor in reprehenderit in voluptate velit esse cillum dolore eu fugiat nulla pariatur. Excepteur sint occaecat cupidatat non proident, sunt in culpa qui officia deserunt mollit anim id est laborum.";
+
+    #[bench]
+    fn way_lorem_ipsum_cached(b: &mut Bencher) {
+        let mut translator = CachingTranslator::new(Style::WAY, DEFAULT_CACHE_CAPACITY);
+        let mut pig_latin_string = Vec::<u8>::with_capacity(LOREM_IPSUM.len() * 2);
+
+        b.iter(|| {
+            pig_latin_string.truncate(0);
+            translator.translate_bytes(test::black_box(LOREM_IPSUM), &mut pig_latin_string);
+        });
+
+        eprintln!("{:?}", translator.stats());//To avoid optimizing things out
+    }
+
+    #[bench]
+    fn way_lorem_ipsum_uncached(b: &mut Bencher) {
+        let mut pig_latin_string = Vec::<u8>::with_capacity(LOREM_IPSUM.len() * 2);
+
+        b.iter(|| {
+            pig_latin_string.truncate(0);
+            translate_byte_string_with(test::black_box(LOREM_IPSUM), &Style::WAY, &mut pig_latin_string);
+        });
+
+        eprintln!("{}", pig_latin_string.len());//To avoid optimizing things out
+    }
+}
@@ -0,0 +1,372 @@
+/* classes.rs
+ * By: John Jekel
+ * Copyright (C) 2022 John Jekel
+ * See the LICENSE file at the root of the project for licensing info.
+ *
+ * A 256-entry table classifying every byte (letter, vowel, y, uppercase, apostrophe, joiner) in a single lookup
+ *
+*/
+
+/* Types */
+
+///Which bytes are vowels, which start contraction suffixes and which join letters into one word, as a 256-entry table that classifies a byte in a single lookup.
+///
+///Tables are built at compile time and used with [`crate::Style::with_classes()`], so custom vowel and apostrophe sets
+///translate exactly as fast as the built-in [`ByteClasses::ENGLISH`] ones.
+///
+///Words are runs of the ASCII letters a-z and A-Z (and any joiners between them, see [`ByteClasses::with_joiners()`]),
+///and y is always treated as a vowel everywhere except at the start of a word.
+///
+///# Examples
+///
+///```
+///use anslatortray::{ByteClasses, Style};
+///
+/////Treat w as a vowel (like in Welsh), and ` as an apostrophe
+///static WELSH: ByteClasses = ByteClasses::new(b"aeiouw", b"'`");
+///const STYLE: Style = Style::WAY.with_classes(&WELSH);
+///
+///# #[cfg(feature = "alloc")]
+///assert_eq!(anslatortray::translate_with("Cwm isn`t it", &STYLE), "Wmcay isnway`t itway");
+///```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct ByteClasses {
+    table: [u8; 256],
+    has_joiners: bool,//The vectorized scanner in scan.rs only knows about letters, so it can't be used if there are joiners
+}
+
+/* Associated Functions and Methods */
+
+impl ByteClasses {
+    //An ASCII letter. Always exactly a-z and A-Z, since the vectorized scanner in scan.rs relies on it
+    pub(crate) const LETTER: u8 = 1 << 0;
+    //A letter that is always a vowel
+    pub(crate) const VOWEL: u8 = 1 << 1;
+    //Y, which is a vowel everywhere except at the start of a word
+    pub(crate) const Y: u8 = 1 << 2;
+    //An uppercase ASCII letter
+    pub(crate) const UPPERCASE: u8 = 1 << 3;
+    //Starts a contraction suffix when directly between a word and more letters (ex. the ' in "isn't")
+    pub(crate) const APOSTROPHE: u8 = 1 << 4;
+    //Part of a word when directly between two letters (ex. the - in "well-known" if it is a joiner)
+    pub(crate) const JOINER: u8 = 1 << 5;
+
+    ///The classes used by default: the vowels a, e, i, o and u, and the apostrophe '.
+    pub const ENGLISH: ByteClasses = ByteClasses::new(b"aeiou", b"'");
+
+    ///Creates a table with the given vowels (ASCII letters, in either case) and apostrophes (ASCII non-letters).
+    ///
+    ///# Panics
+    ///
+    ///Panics if a vowel isn't an ASCII letter, or if an apostrophe is an ASCII letter or isn't ASCII.
+    pub const fn new(vowels: &[u8], apostrophes: &[u8]) -> ByteClasses {
+        let mut table = [0u8; 256];
+
+        let mut letter = b'A';
+        while letter <= b'Z' {
+            table[letter as usize] |= ByteClasses::LETTER | ByteClasses::UPPERCASE;
+            table[letter.to_ascii_lowercase() as usize] |= ByteClasses::LETTER;
+            letter += 1;
+        }
+        table[b'y' as usize] |= ByteClasses::Y;
+        table[b'Y' as usize] |= ByteClasses::Y;
+
+        let mut i = 0;
+        while i < vowels.len() {
+            if !vowels[i].is_ascii_alphabetic() {
+                panic!("anslatortray vowels must be ASCII letters");
+            }
+            table[vowels[i].to_ascii_lowercase() as usize] |= ByteClasses::VOWEL;
+            table[vowels[i].to_ascii_uppercase() as usize] |= ByteClasses::VOWEL;
+            i += 1;
+        }
+        i = 0;
+        while i < apostrophes.len() {
+            if !apostrophes[i].is_ascii() || apostrophes[i].is_ascii_alphabetic() {
+                panic!("anslatortray apostrophes must be ASCII characters other than letters");
+            }
+            table[apostrophes[i] as usize] |= ByteClasses::APOSTROPHE;
+            i += 1;
+        }
+
+        return ByteClasses { table, has_joiners: false };
+    }
+
+    ///Returns a copy of this table where the given bytes (ASCII punctuation) join the letters on either side of them into one word.
+    ///
+    ///Joiners are translated like consonants that are never uppercase, and only when directly between two letters:
+    ///anywhere else they separate words like any other punctuation.
+    ///
+    ///# Panics
+    ///
+    ///Panics if a joiner isn't ASCII punctuation, or is also an apostrophe.
+    ///
+    ///# Examples
+    ///
+    ///```
+    ///use anslatortray::{ByteClasses, Style};
+    ///
+    ///static HYPHENATED: ByteClasses = ByteClasses::ENGLISH.with_joiners(b"-");
+    ///const STYLE: Style = Style::WAY.with_classes(&HYPHENATED);
+    ///
+    ///# #[cfg(feature = "alloc")]
+    ///assert_eq!(anslatortray::translate_with("A well-known, -odd- word", &STYLE), "Away ell-knownway, -oddway- ordway");
+    ///```
+    pub const fn with_joiners(mut self, joiners: &[u8]) -> ByteClasses {
+        let mut i = 0;
+        while i < joiners.len() {
+            if !joiners[i].is_ascii_punctuation() || self.is_apostrophe(joiners[i]) {
+                panic!("anslatortray joiners must be ASCII punctuation other than apostrophes");
+            }
+            self.table[joiners[i] as usize] |= ByteClasses::JOINER;
+            self.has_joiners = true;
+            i += 1;
+        }
+
+        return self;
+    }
+
+    //Whether the byte is in any of the given classes
+    #[inline(always)]
+    pub(crate) const fn is(&self, byte: u8, classes: u8) -> bool {
+        return (self.table[byte as usize] & classes) != 0;
+    }
+
+    #[inline(always)]
+    pub(crate) const fn is_letter(&self, byte: u8) -> bool {
+        return self.is(byte, ByteClasses::LETTER);
+    }
+
+    #[inline(always)]
+    pub(crate) const fn is_vowel(&self, byte: u8) -> bool {
+        return self.is(byte, ByteClasses::VOWEL);
+    }
+
+    #[inline(always)]
+    pub(crate) const fn is_vowel_or_y(&self, byte: u8) -> bool {
+        return self.is(byte, ByteClasses::VOWEL | ByteClasses::Y);
+    }
+
+    #[inline(always)]
+    pub(crate) const fn is_uppercase(&self, byte: u8) -> bool {
+        return self.is(byte, ByteClasses::UPPERCASE);
+    }
+
+    #[inline(always)]
+    pub(crate) const fn is_apostrophe(&self, byte: u8) -> bool {
+        return self.is(byte, ByteClasses::APOSTROPHE);
+    }
+
+    #[inline(always)]
+    pub(crate) const fn is_joiner(&self, byte: u8) -> bool {
+        return self.is(byte, ByteClasses::JOINER);
+    }
+
+    #[inline(always)]
+    pub(crate) const fn has_joiners(&self) -> bool {
+        return self.has_joiners;
+    }
+}
+
+impl Default for ByteClasses {
+    fn default() -> ByteClasses {
+        return ByteClasses::ENGLISH;
+    }
+}
+
+//Printing all 256 entries isn't very useful, so just list the configurable classes
+impl core::fmt::Debug for ByteClasses {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        return f.debug_struct("ByteClasses")
+            .field("vowels", &ClassMembers(self, ByteClasses::VOWEL))
+            .field("apostrophes", &ClassMembers(self, ByteClasses::APOSTROPHE))
+            .field("joiners", &ClassMembers(self, ByteClasses::JOINER))
+            .finish();
+    }
+}
+
+//The bytes in a class, formatted as a list of characters
+struct ClassMembers<'a>(&'a ByteClasses, u8);
+
+impl core::fmt::Debug for ClassMembers<'_> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        return f.debug_list().entries((0..=255u8).filter(|&byte| self.0.is(byte, self.1)).map(char::from)).finish();
+    }
+}
+
+/* Tests */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ENGLISH: ByteClasses = ByteClasses::ENGLISH;
+
+    #[test]
+    fn test_english_classes() {
+        for byte in 0..=255u8 {
+            assert_eq!(ENGLISH.is_letter(byte), byte.is_ascii_alphabetic(), "{}", byte);
+            assert_eq!(ENGLISH.is_uppercase(byte), byte.is_ascii_uppercase(), "{}", byte);
+            assert_eq!(ENGLISH.is_vowel(byte), b"aeiouAEIOU".contains(&byte), "{}", byte);
+            assert_eq!(ENGLISH.is_vowel_or_y(byte), b"aeiouyAEIOUY".contains(&byte), "{}", byte);
+            assert_eq!(ENGLISH.is_apostrophe(byte), byte == b'\'', "{}", byte);
+            assert!(!ENGLISH.is_joiner(byte), "{}", byte);
+        }
+    }
+
+    #[test]
+    fn test_is_vowel() {
+        for letter in b"aeiouAEIOU".iter() {
+            assert!(ENGLISH.is_vowel(*letter));
+        }
+
+        for letter in b"bcdfghjklmnpqrstvwxyzBCDFGHJKLMNPQRSTVWXYZ".iter() {
+            assert!(!ENGLISH.is_vowel(*letter));
+        }
+
+        for not_letter in b" !@#$%^&*()_+={}|\":>?~`\\][';/.,\t\n".iter() {
+            assert!(!ENGLISH.is_vowel(*not_letter));
+        }
+    }
+
+    #[test]
+    fn test_is_y() {
+        for letter in b"yY".iter() {
+            assert!(ENGLISH.is(*letter, ByteClasses::Y));
+        }
+
+        for letter in b"abcdefghijklmnopqrstuvwxzABCDEFGHIJKLMNOPQRSTUVWXZ".iter() {
+            assert!(!ENGLISH.is(*letter, ByteClasses::Y));
+        }
+
+        for not_letter in b" !@#$%^&*()_+={}|\":>?~`\\][';/.,\t\n".iter() {
+            assert!(!ENGLISH.is(*not_letter, ByteClasses::Y));
+        }
+    }
+
+    #[test]
+    fn test_is_uppercase() {
+        for letter in b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ".iter() {
+            assert_eq!(ENGLISH.is_uppercase(*letter), letter.is_ascii_uppercase());
+        }
+    }
+
+    #[test]
+    fn test_custom_classes() {
+        let classes = ByteClasses::new(b"aeiouW", b"'`");
+        assert!(classes.is_vowel(b'w') && classes.is_vowel(b'W') && classes.is_letter(b'w'));
+        assert!(classes.is_apostrophe(b'`') && !ENGLISH.is_apostrophe(b'`'));
+        assert_eq!(ByteClasses::default(), ENGLISH);
+        assert_eq!(format!("{:?}", classes), "ByteClasses { vowels: ['A', 'E', 'I', 'O', 'U', 'W', 'a', 'e', 'i', 'o', 'u', 'w'], apostrophes: ['\\'', '`'], joiners: [] }");
+    }
+
+    #[test]
+    fn test_joiners() {
+        let classes = ByteClasses::ENGLISH.with_joiners(b"-.");
+        assert!(classes.is_joiner(b'-') && classes.is_joiner(b'.') && !classes.is_joiner(b','));
+        assert!(!classes.is_letter(b'-') && classes.is_apostrophe(b'\''));
+        assert!(classes.has_joiners() && !ENGLISH.has_joiners() && !ENGLISH.with_joiners(b"").has_joiners());
+        assert_ne!(classes, ENGLISH);
+        assert_eq!(format!("{:?}", classes), "ByteClasses { vowels: ['A', 'E', 'I', 'O', 'U', 'a', 'e', 'i', 'o', 'u'], apostrophes: ['\\''], joiners: ['-', '.'] }");
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_joiner_whitespace() {
+        let _ = ByteClasses::ENGLISH.with_joiners(b" ");
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_joiner_apostrophe() {
+        let _ = ByteClasses::ENGLISH.with_joiners(b"-'");
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_custom_classes_non_letter_vowel() {
+        ByteClasses::new(b"aeiou1", b"'");
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_custom_classes_letter_apostrophe() {
+        ByteClasses::new(b"aeiou", b"'s");
+    }
+}
+
+/* Benches */
+
+#[cfg_attr(feature = "nightly-features-benches", cfg(test))]
+#[cfg(feature = "nightly-features-benches")]
+mod benches {
+    extern crate test;
+    use test::Bencher;
+    use super::*;
+
+    const LOREM_IPSUM: &[u8] = b"Lorem ipsum dolor sit amet, consectetur adipiscing elit, sed do eiusmod tempor incididunt ut labore et dolore magna aliqua. Ut enim ad minim veniam, quis nostrud exercitation ullamco laboris nisi ut aliquip ex ea commodo consequat. Duis aute irure dolor in reprehenderit in voluptate velit esse cillum dolore eu fugiat nulla pariatur. Excepteur sint occaecat cupidatat non proident, sunt in culpa qui officia deserunt mollit anim id est laborum.";
+    const MIXED: &[u8] = b"Let's try some edge cases: ISn't THIS COOL? What ABOUT a MIX, mcDONALD? Hyphens-are-difficult-aren't-they? Why, my gym's rhythm is SHY! 1234-5678 (42%)";
+
+    //The hand-written checks the translation loop used before the table (fast_is_ascii_uppercase is only correct for letters)
+    fn is_vowel(letter: u8) -> bool {
+        match letter {
+            b'a' | b'e' | b'i' | b'o' | b'u' | b'A' | b'E' | b'I' | b'O' | b'U' => { return true; }
+            _ => { return false; }
+        }
+    }
+
+    fn is_y(letter: u8) -> bool {
+        return (letter == b'y') || (letter == b'Y');
+    }
+
+    fn fast_is_ascii_uppercase(letter: u8) -> bool {
+        return letter <= b'Z';
+    }
+
+    //Counts (letters, vowels, vowels or y, uppercase letters), asking the same questions the translation loop does
+    fn classify_with_table(english: &[u8], classes: &ByteClasses) -> (usize, usize, usize, usize) {
+        let mut counts = (0, 0, 0, 0);
+        for &byte in english {
+            if classes.is_letter(byte) {
+                counts.0 += 1;
+                counts.1 += classes.is_vowel(byte) as usize;
+                counts.2 += classes.is_vowel_or_y(byte) as usize;
+                counts.3 += classes.is_uppercase(byte) as usize;
+            }
+        }
+        return counts;
+    }
+
+    fn classify_by_hand(english: &[u8]) -> (usize, usize, usize, usize) {
+        let mut counts = (0, 0, 0, 0);
+        for &byte in english {
+            if byte.is_ascii_alphabetic() {
+                counts.0 += 1;
+                counts.1 += is_vowel(byte) as usize;
+                counts.2 += (is_vowel(byte) || is_y(byte)) as usize;
+                counts.3 += fast_is_ascii_uppercase(byte) as usize;
+            }
+        }
+        return counts;
+    }
+
+    #[bench]
+    fn table_lorem_ipsum(b: &mut Bencher) {
+        b.iter(|| -> (usize, usize, usize, usize) { return classify_with_table(test::black_box(LOREM_IPSUM), test::black_box(&ByteClasses::ENGLISH)); });
+    }
+
+    #[bench]
+    fn hand_written_lorem_ipsum(b: &mut Bencher) {
+        b.iter(|| -> (usize, usize, usize, usize) { return classify_by_hand(test::black_box(LOREM_IPSUM)); });
+    }
+
+    #[bench]
+    fn table_mixed(b: &mut Bencher) {
+        b.iter(|| -> (usize, usize, usize, usize) { return classify_with_table(test::black_box(MIXED), test::black_box(&ByteClasses::ENGLISH)); });
+    }
+
+    #[bench]
+    fn hand_written_mixed(b: &mut Bencher) {
+        b.iter(|| -> (usize, usize, usize, usize) { return classify_by_hand(test::black_box(MIXED)); });
+    }
+}
@@ -0,0 +1,149 @@
+/* diagnostics.rs
+ * By: John Jekel
+ * Copyright (C) 2022 John Jekel
+ * See the LICENSE file at the root of the project for licensing info.
+ *
+ * Flags words whose translation relied on a heuristic fallback, so they can be reviewed by hand
+ *
+*/
+
+/* Imports */
+
+use core::ops::Range;
+
+use alloc::vec::Vec;
+
+use crate::classes::ByteClasses;
+use crate::spans::{Span, SpanKind};
+
+/* Constants */
+
+//All-uppercase words at most this long are considered acronym-like
+const MAX_ACRONYM_LEN: usize = 5;
+
+/* Types */
+
+///A spot in a translation that relied on a heuristic and may be worth double-checking.
+///
+///See [`crate::translate_with_diagnostics()`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Diagnostic {
+    ///What kind of heuristic was used
+    pub kind: DiagnosticKind,
+    ///The byte range of the word (including any contraction suffix) in the English text
+    pub english: Range<usize>,
+    ///The byte range of the translated word (including any contraction suffix) in the Pig Latin text
+    pub pig_latin: Range<usize>,
+}
+
+///The reason a [`Diagnostic`] was reported.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum DiagnosticKind {
+    ///The word has no vowels (ex. "psst"), so it was left as-is with the suffix added.
+    NoVowel,
+    ///The word's letters are neither all lowercase, capitalized, nor all uppercase (ex. "ISn't" or "mcDONALD"),
+    ///so whether its translation is treated as uppercase was guessed.
+    AmbiguousCase,
+    ///The word is short and all uppercase (ex. "NASA"), so it may be an acronym that shouldn't be translated.
+    AcronymLike,
+    ///The word is directly next to a non-ASCII letter (ex. the "caf" in "café"), so only part of it was translated.
+    NonAsciiLetter,
+    ///The word has a contraction suffix (ex. the 't in "isn't") that was copied as-is after the translated word.
+    ContractionSuffix,
+}
+
+/* Functions */
+
+//Checks a word span (and the contraction suffix span after it, if any), appending any diagnostics found
+pub(crate) fn check_word(english: &[u8], word: &Span, contraction_suffix: Option<&Span>, pig_latin: Range<usize>, classes: &ByteClasses, diagnostics: &mut Vec<Diagnostic>) {
+    debug_assert!(word.kind == SpanKind::Word);
+
+    let word_slice = &english[word.range.clone()];
+    let english_range = match contraction_suffix {
+        Some(contraction_suffix) => word.range.start..contraction_suffix.range.end,
+        None => word.range.clone(),
+    };
+    let mut report = |kind: DiagnosticKind| {
+        diagnostics.push(Diagnostic { kind, english: english_range.clone(), pig_latin: pig_latin.clone() });
+    };
+
+    if (word_slice.len() > 1) && !word_slice.iter().any(|&letter| classes.is_vowel_or_y(letter)) {
+        report(DiagnosticKind::NoVowel);
+    }
+
+    //Words are only ever letters, so anything that isn't uppercase is lowercase
+    let all_uppercase = word_slice.iter().all(|&letter| classes.is_uppercase(letter));
+    let all_lowercase = !word_slice.iter().any(|&letter| classes.is_uppercase(letter));
+    let capitalized = !word_slice[1..].iter().any(|&letter| classes.is_uppercase(letter));
+    if !all_uppercase && !all_lowercase && !capitalized {
+        report(DiagnosticKind::AmbiguousCase);
+    }
+
+    if all_uppercase && (word_slice.len() > 1) && (word_slice.len() <= MAX_ACRONYM_LEN) {
+        report(DiagnosticKind::AcronymLike);
+    }
+
+    let end = english_range.end;
+    if non_ascii_letter_ends_at(english, word.range.start) || non_ascii_letter_starts_at(english, end) {
+        report(DiagnosticKind::NonAsciiLetter);
+    }
+
+    if contraction_suffix.is_some() {
+        report(DiagnosticKind::ContractionSuffix);
+    }
+}
+
+//Whether the (possibly invalid) UTF-8 character starting at index is a non-ASCII letter
+fn non_ascii_letter_starts_at(english: &[u8], index: usize) -> bool {
+    if (index >= english.len()) || english[index].is_ascii() {
+        return false;
+    }
+
+    let window = &english[index..english.len().min(index + 4)];
+    let valid_prefix = match core::str::from_utf8(window) {
+        Ok(valid) => valid,
+        Err(error) => core::str::from_utf8(&window[..error.valid_up_to()]).unwrap_or(""),
+    };
+    return valid_prefix.chars().next().is_some_and(|character| character.is_alphabetic());
+}
+
+//Whether the (possibly invalid) UTF-8 character ending just before index is a non-ASCII letter
+fn non_ascii_letter_ends_at(english: &[u8], index: usize) -> bool {
+    if (index == 0) || english[index - 1].is_ascii() {
+        return false;
+    }
+
+    //Walk back over continuation bytes to find the start of the character
+    let mut start = index - 1;
+    while (start > 0) && (index - start < 4) && ((english[start] & 0b1100_0000) == 0b1000_0000) {
+        start -= 1;
+    }
+    return match core::str::from_utf8(&english[start..index]) {
+        Ok(character) => character.chars().next().is_some_and(|character| character.is_alphabetic()),
+        Err(_) => false,
+    };
+}
+
+/* Tests */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_non_ascii_letter_boundaries() {
+        let english = "café naïve 5€".as_bytes();
+
+        assert!(non_ascii_letter_starts_at(english, 3));//é
+        assert!(!non_ascii_letter_starts_at(english, 2));//f
+        assert!(non_ascii_letter_ends_at(english, 5));//After é
+        assert!(non_ascii_letter_ends_at(english, 10));//After ï
+        assert!(!non_ascii_letter_starts_at(english, english.len() - 3));//€ isn't a letter
+        assert!(!non_ascii_letter_ends_at(english, english.len()));
+
+        //Truncated or invalid UTF-8 is never considered a letter
+        assert!(!non_ascii_letter_starts_at(&english[..4], 3));
+        assert!(!non_ascii_letter_ends_at(b"ab\x80cd", 3));
+    }
+}
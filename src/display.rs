@@ -0,0 +1,108 @@
+/* display.rs
+ * By: John Jekel
+ * Copyright (C) 2022 John Jekel
+ * See the LICENSE file at the root of the project for licensing info.
+ *
+ * Translation straight into a fmt::Formatter, without allocating
+ *
+*/
+
+/* Imports */
+
+use core::fmt::{self, Write};
+
+use crate::byte_string::analyze_word;
+use crate::spans::{Spans, SpanKind};
+use crate::style::Style;
+use crate::word::Rule;
+
+/* Types */
+
+///Translates a multi-word string (including punctuation) when it is formatted, without allocating!
+///
+///The translation is written piece by piece straight into the [`fmt::Formatter`], so this is the cheapest way to
+///translate text that is only going to be printed, logged or passed to `write!` once.
+///The result is the same as [`crate::translate_with()`].
+///
+///# Examples
+///
+///```
+///use std::fmt::Write;
+///use anslatortray::{PigLatin, Style};
+///
+///assert_eq!(format!("{}", PigLatin("Hello world!", &Style::WAY)), "Ellohay orldway!");
+///assert_eq!(PigLatin("Where's Perry?", &Style::FERB).to_string(), "Erewherb's Erryperb?");
+///
+///let mut log = String::new();
+///write!(log, "[info] {}", PigLatin("Nothing to see here", &Style::HAY)).unwrap();
+///assert_eq!(log, "[info] Othingnay otay eesay erehay");
+///```
+#[derive(Clone, Copy, Debug)]
+pub struct PigLatin<'a>(pub &'a str, pub &'a Style);
+
+/* Associated Functions and Methods */
+
+impl fmt::Display for PigLatin<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let PigLatin(english, style) = *self;
+
+        for span in Spans::new(english.as_bytes(), style.classes()) {
+            //Spans always start and end next to ASCII letters (or the ends of the string), so they lie on char boundaries
+            let text = &english[span.range];
+            if span.kind == SpanKind::Word {
+                write_word(f, text, style)?;
+            } else {
+                f.write_str(text)?;
+            }
+        }
+
+        return Ok(());
+    }
+}
+
+/* Functions */
+
+//The same as byte_string's translate_word_with_style_reuse_buffers, but writing pieces of the word instead of appending to a buffer
+fn write_word(f: &mut fmt::Formatter<'_>, english_word: &str, style: &Style) -> fmt::Result {
+    let analysis = analyze_word(english_word.as_bytes(), style.word_rules());
+
+    let suffix = analysis.suffix(style);
+
+    if analysis.rule == Rule::ConsonantCluster {
+        let (onset, rime) = english_word.split_at(analysis.onset_len);
+        let first_vowel = rime.as_bytes()[0];
+        let first_letter = onset.as_bytes()[0];
+
+        //The word is ASCII, so each letter is a single char (and everything after it is on a char boundary)
+        f.write_char(if analysis.capitalize_rime { first_vowel.to_ascii_uppercase() } else { first_vowel } as char)?;
+        f.write_str(&rime[1..])?;
+        f.write_char(if analysis.lowercase_onset { first_letter.to_ascii_lowercase() } else { first_letter } as char)?;
+        f.write_str(&onset[1..])?;
+    } else {
+        f.write_str(english_word)?;
+    }
+
+    return f.write_str(suffix);
+}
+
+/* Tests */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::style::RulesVersion;
+
+    #[test]
+    fn test_pig_latin_display_matches_translate_to_slice() {
+        const ENGLISH: &str = "Let's try some edge cases: ISn't THIS COOL? naïve mcDONALD's, bcd 123 Hyphens-are-difficult-aren't-they?' A";
+
+        static HYPHENATED: crate::ByteClasses = crate::ByteClasses::ENGLISH.with_joiners(b"-");
+
+        for style in [Style::WAY, Style::YAY, Style::FERB, Style::new("äy", "wäy"), Style::HAY.with_rules(RulesVersion::V0_4), Style::WAY.with_classes(&HYPHENATED)] {
+            let mut buffer = [0u8; 256];
+            let len = crate::byte_string::translate_to_slice(ENGLISH.as_bytes(), &style, &mut buffer).unwrap();
+            assert_eq!(PigLatin(ENGLISH, &style).to_string().as_bytes(), &buffer[..len]);
+        }
+        assert_eq!(PigLatin("", &Style::WAY).to_string(), "");
+    }
+}
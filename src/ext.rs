@@ -0,0 +1,200 @@
+/* ext.rs
+ * By: John Jekel
+ * Copyright (C) 2022 John Jekel
+ * See the LICENSE file at the root of the project for licensing info.
+ *
+ * Extension traits adding translation methods to str, String and Cow<str>
+ *
+*/
+
+/* Imports */
+
+use alloc::borrow::Cow;
+use alloc::string::String;
+#[cfg(feature = "safe")]
+use alloc::vec::Vec;
+
+use crate::byte_string::translate_with as translate_byte_string_with;
+use crate::style::Style;
+#[cfg(feature = "safe")]
+use crate::utf8::translated_str;
+
+/* Types */
+
+///Adds Pig Latin translation methods to [`str`], [`String`] and [`Cow<str>`].
+///
+///# Examples
+///
+///```
+///use std::borrow::Cow;
+///use anslatortray::{PigLatinExt, Style};
+///
+///assert_eq!("Hello world!".to_pig_latin(), "Ellohay orldway!");
+///assert_eq!(String::from("Where's Perry?").to_pig_latin_with(&Style::FERB), "Erewherb's Erryperb?");
+///assert_eq!(Cow::Borrowed("Oink oink").to_pig_latin(), "Oinkway oinkway");
+///
+/////Reuse the same buffer for many translations
+///let mut pig_latin = String::new();
+///for line in ["one", "two", "three"] {
+///    pig_latin.truncate(0);
+///    line.pig_latin_into(&mut pig_latin);
+///    assert!(pig_latin.ends_with("ay"));
+///}
+///```
+pub trait PigLatinExt {
+    ///Translates into Pig Latin like [`crate::translate()`].
+    fn to_pig_latin(&self) -> String;
+
+    ///Translates into Pig Latin with the given [`Style`] like [`crate::translate_with()`].
+    fn to_pig_latin_with(&self, style: &Style) -> String;
+
+    ///Translates into Pig Latin like [`crate::translate()`], appending the translation to `pig_latin`.
+    fn pig_latin_into(&self, pig_latin: &mut String);
+
+    ///Translates into Pig Latin with the given [`Style`] like [`crate::translate_with()`], appending the translation to `pig_latin`.
+    fn pig_latin_into_with(&self, style: &Style, pig_latin: &mut String);
+}
+
+///Adds methods to translate a [`String`] in-place.
+///
+///# Examples
+///
+///```
+///use anslatortray::{MakePigLatin, Style};
+///
+///let mut text = String::from("Hello world!");
+///text.make_pig_latin();
+///assert_eq!(text, "Ellohay orldway!");
+///
+///let mut text = String::from("Where's Perry?");
+///text.make_pig_latin_with(&Style::FERB);
+///assert_eq!(text, "Erewherb's Erryperb?");
+///```
+pub trait MakePigLatin {
+    ///Replaces the contents of the string with their translation, like [`crate::translate()`].
+    fn make_pig_latin(&mut self);
+
+    ///Replaces the contents of the string with their translation using the given [`Style`], like [`crate::translate_with()`].
+    fn make_pig_latin_with(&mut self, style: &Style);
+}
+
+/* Associated Functions and Methods */
+
+impl PigLatinExt for str {
+    fn to_pig_latin(&self) -> String {
+        return self.to_pig_latin_with(&Style::WAY);
+    }
+
+    fn to_pig_latin_with(&self, style: &Style) -> String {
+        let mut pig_latin = String::with_capacity(self.len() * 2);//Plenty of headroom in case the words are very small or the suffixes are long
+        self.pig_latin_into_with(style, &mut pig_latin);
+        return pig_latin;
+    }
+
+    fn pig_latin_into(&self, pig_latin: &mut String) {
+        self.pig_latin_into_with(&Style::WAY, pig_latin);
+    }
+
+    fn pig_latin_into_with(&self, style: &Style, pig_latin: &mut String) {
+        #[cfg(feature = "safe")]
+        {
+            //Translate into a separate buffer so only the new part needs to be checked
+            let mut pig_latin_bytes = Vec::<u8>::with_capacity(self.len() * 2);//Plenty of headroom in case the words are very small or the suffixes are long
+            translate_byte_string_with(self.as_bytes(), style, &mut pig_latin_bytes);
+            pig_latin.push_str(translated_str(&pig_latin_bytes));
+        }
+        #[cfg(not(feature = "safe"))]
+        {
+            //This is safe since translate_byte_string_with only appends, does not touch any unicode bytes (it just copies them), and the suffixes came from &strs
+            let pig_latin_bytes = unsafe { pig_latin.as_mut_vec() };
+            translate_byte_string_with(self.as_bytes(), style, pig_latin_bytes);
+        }
+    }
+}
+
+impl PigLatinExt for String {
+    fn to_pig_latin(&self) -> String {
+        return self.as_str().to_pig_latin();
+    }
+
+    fn to_pig_latin_with(&self, style: &Style) -> String {
+        return self.as_str().to_pig_latin_with(style);
+    }
+
+    fn pig_latin_into(&self, pig_latin: &mut String) {
+        self.as_str().pig_latin_into(pig_latin);
+    }
+
+    fn pig_latin_into_with(&self, style: &Style, pig_latin: &mut String) {
+        self.as_str().pig_latin_into_with(style, pig_latin);
+    }
+}
+
+impl PigLatinExt for Cow<'_, str> {
+    fn to_pig_latin(&self) -> String {
+        return self.as_ref().to_pig_latin();
+    }
+
+    fn to_pig_latin_with(&self, style: &Style) -> String {
+        return self.as_ref().to_pig_latin_with(style);
+    }
+
+    fn pig_latin_into(&self, pig_latin: &mut String) {
+        self.as_ref().pig_latin_into(pig_latin);
+    }
+
+    fn pig_latin_into_with(&self, style: &Style, pig_latin: &mut String) {
+        self.as_ref().pig_latin_into_with(style, pig_latin);
+    }
+}
+
+impl MakePigLatin for String {
+    fn make_pig_latin(&mut self) {
+        self.make_pig_latin_with(&Style::WAY);
+    }
+
+    fn make_pig_latin_with(&mut self, style: &Style) {
+        //The translation is longer than the original, so it can't overwrite it as it goes; translate into a new buffer and swap it in
+        let english = core::mem::replace(self, String::with_capacity(self.len() * 2));
+        english.pig_latin_into_with(style, self);
+    }
+}
+
+/* Tests */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::style::RulesVersion;
+
+    const ENGLISH: &str = "Let's try some edge cases: ISn't THIS COOL? naïve mcDONALD's, bcd 123 Hyphens-are-difficult-aren't-they?'";
+
+    #[test]
+    fn test_pig_latin_ext_matches_translate_with() {
+        for style in [Style::WAY, Style::FERB, Style::new("äy", "wäy"), Style::HAY.with_rules(RulesVersion::V0_4)] {
+            let expected = crate::translate_with(ENGLISH, &style);
+
+            assert_eq!(ENGLISH.to_pig_latin_with(&style), expected);
+            assert_eq!(ENGLISH.to_string().to_pig_latin_with(&style), expected);
+            assert_eq!(Cow::Borrowed(ENGLISH).to_pig_latin_with(&style), expected);
+            assert_eq!(Cow::<str>::Owned(ENGLISH.to_string()).to_pig_latin_with(&style), expected);
+
+            let mut text = ENGLISH.to_string();
+            text.make_pig_latin_with(&style);
+            assert_eq!(text, expected);
+        }
+        assert_eq!(ENGLISH.to_pig_latin(), crate::translate(ENGLISH));
+    }
+
+    #[test]
+    fn test_pig_latin_into_appends() {
+        let mut pig_latin = String::from("> ");
+        "Hello".pig_latin_into(&mut pig_latin);
+        String::from(" world").pig_latin_into(&mut pig_latin);
+        assert_eq!(pig_latin, "> Ellohay orldway");
+
+        let mut empty = String::new();
+        empty.make_pig_latin();
+        assert_eq!(empty, "");
+    }
+}
@@ -0,0 +1,163 @@
+/* hook.rs
+ * By: John Jekel
+ * Copyright (C) 2022 John Jekel
+ * See the LICENSE file at the root of the project for licensing info.
+ *
+ * Translation with a callback that decides what to do with each word
+ *
+*/
+
+/* Imports */
+
+use core::ops::Range;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::byte_string::{analyze_word, translate_with as translate_byte_string_with};
+use crate::spans::{Spans, SpanKind};
+use crate::style::Style;
+use crate::utf8::translated_string;
+use crate::word::CaseClass;
+
+/* Types */
+
+///A word about to be translated, and where it is in the text; see [`translate_with_hook()`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct WordInfo<'a> {
+    ///The word (only ASCII letters, not including any contraction suffix like the 't in "isn't")
+    pub word: &'a str,
+    ///The whole text being translated
+    pub english: &'a str,
+    ///The byte range of the word in the text
+    pub range: Range<usize>,
+    ///How many words came before this one
+    pub index: usize,
+    ///How the capitalization of the word is interpreted by the [`Style`]'s rules
+    pub case: CaseClass,
+}
+
+///What [`translate_with_hook()`] should do with a word.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Decision {
+    ///Translate the word as usual
+    Translate,
+    ///Copy the word as-is
+    Keep,
+    ///Output this text instead of the word
+    Replace(String),
+    ///Translate the word using a different [`Style`] (the word itself was still found using the [`Style`] passed to [`translate_with_hook()`])
+    UseStyle(Style),
+}
+
+/* Associated Functions and Methods */
+
+impl<'a> WordInfo<'a> {
+    ///Returns the text before the word.
+    pub fn before(&self) -> &'a str {
+        return &self.english[..self.range.start];
+    }
+
+    ///Returns the text after the word (starting with its contraction suffix, if any).
+    pub fn after(&self) -> &'a str {
+        return &self.english[self.range.end..];
+    }
+}
+
+/* Functions */
+
+///Translates a multi-word string (including punctuation) using a [`Style`], letting a callback decide what to do with each word!
+///
+///The callback is called once per word, in order, with a [`WordInfo`] describing the word and its surroundings, and returns a [`Decision`].
+///This covers one-off requirements (skipping names, leaving quoted text alone, house rules, etc.) without reimplementing the translation loop.
+///Everything in-between words (including contraction suffixes) is copied as-is, like [`crate::translate_with()`].
+///
+///# Examples
+///
+///```
+///use anslatortray::{translate_with_hook, Style, Decision, CaseClass};
+///
+/////Leave names (and anything shouting) alone, and spell out "and"
+///let pig_latin = translate_with_hook("Alice and Bob ATE pie", &Style::WAY, |word| {
+///    return match word.word {
+///        "Alice" | "Bob" => Decision::Keep,
+///        "and" => Decision::Replace("andway".to_string()),
+///        _ if word.case == CaseClass::Upper => Decision::Keep,
+///        _ => Decision::UseStyle(Style::FERB),
+///    };
+///});
+///assert_eq!(pig_latin, "Alice andway Bob ATE ieperb");
+///
+/////Skip words inside quotes, using the context before each word
+///let pig_latin = translate_with_hook("He said \"hello there\" twice", &Style::WAY, |word| {
+///    let quoted = word.before().matches('"').count() % 2 == 1;
+///    return if quoted { Decision::Keep } else { Decision::Translate };
+///});
+///assert_eq!(pig_latin, "Ehay aidsay \"hello there\" icetway");
+///```
+pub fn translate_with_hook<'a, F>(english: &'a str, style: &Style, mut hook: F) -> String
+where
+    F: FnMut(&WordInfo<'a>) -> Decision,
+{
+    let mut pig_latin_string_bytes = Vec::<u8>::with_capacity(english.len() * 2);//Plenty of headroom in case the words are very small or the suffixes are long
+
+    let mut index: usize = 0;
+    for span in Spans::new(english.as_bytes(), style.classes()) {
+        //Spans always start and end next to ASCII letters (or the ends of the string), so they lie on char boundaries
+        let text = &english[span.range.clone()];
+        if span.kind != SpanKind::Word {
+            pig_latin_string_bytes.extend_from_slice(text.as_bytes());
+            continue;
+        }
+
+        let info = WordInfo { word: text, english, range: span.range, index, case: analyze_word(text.as_bytes(), style.word_rules()).case };
+        match hook(&info) {
+            Decision::Translate => translate_byte_string_with(text.as_bytes(), style, &mut pig_latin_string_bytes),
+            Decision::Keep => pig_latin_string_bytes.extend_from_slice(text.as_bytes()),
+            Decision::Replace(replacement) => pig_latin_string_bytes.extend_from_slice(replacement.as_bytes()),
+            Decision::UseStyle(other_style) => translate_byte_string_with(text.as_bytes(), &other_style, &mut pig_latin_string_bytes),
+        }
+        index += 1;
+    }
+
+    return translated_string(pig_latin_string_bytes);
+}
+
+/* Tests */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::style::RulesVersion;
+
+    const ENGLISH: &str = "Let's try some edge cases: ISn't THIS COOL? naïve mcDONALD's, bcd 123 Hyphens-are-difficult-aren't-they?' A";
+
+    #[test]
+    fn test_translate_with_hook_translate_matches_translate_with() {
+        static HYPHENATED: crate::ByteClasses = crate::ByteClasses::ENGLISH.with_joiners(b"-");
+        for style in [Style::WAY, Style::FERB, Style::new("äy", "wäy"), Style::HAY.with_rules(RulesVersion::V0_4), Style::WAY.with_classes(&HYPHENATED)] {
+            assert_eq!(translate_with_hook(ENGLISH, &style, |_| Decision::Translate), crate::translate_with(ENGLISH, &style));
+            //The words themselves are still found with Style::WAY's classes
+            if style.classes() == Style::WAY.classes() {
+                assert_eq!(translate_with_hook(ENGLISH, &Style::WAY, |_| Decision::UseStyle(style)), crate::translate_with(ENGLISH, &style));
+            }
+        }
+        assert_eq!(translate_with_hook(ENGLISH, &Style::WAY, |_| Decision::Keep), ENGLISH);
+    }
+
+    #[test]
+    fn test_translate_with_hook_word_info() {
+        let mut words = Vec::new();
+        let pig_latin = translate_with_hook("Isn't it, NASA?", &Style::WAY, |word| {
+            words.push((word.word, word.range.clone(), word.index, word.case, word.before(), word.after()));
+            return Decision::Replace(word.index.to_string());
+        });
+
+        assert_eq!(pig_latin, "0't 1, 2?");
+        assert_eq!(words, [
+            ("Isn", 0..3, 0, CaseClass::Title, "", "'t it, NASA?"),
+            ("it", 6..8, 1, CaseClass::Lower, "Isn't ", ", NASA?"),
+            ("NASA", 10..14, 2, CaseClass::Upper, "Isn't it, ", "?"),
+        ]);
+    }
+}
@@ -0,0 +1,274 @@
+/* incremental.rs
+ * By: John Jekel
+ * Copyright (C) 2022 John Jekel
+ * See the LICENSE file at the root of the project for licensing info.
+ *
+ * Keeps a translation up to date as its English text is edited, retranslating only the words an edit touches
+ *
+*/
+
+/* Imports */
+
+use core::ops::Range;
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::byte_string::translate_with as translate_byte_string_with;
+use crate::spans::{Spans, SpanKind};
+use crate::style::Style;
+use crate::utf8::translated_str;
+
+/* Types */
+
+///Keeps the translation of a document up to date as it is edited; see [`IncrementalTranslator::edit()`].
+///
+///The word boundaries of the document are remembered, so an edit only retranslates the words around it instead of the whole document.
+///The translation is always the same as [`crate::translate_with()`] would give for the current text.
+///
+///# Examples
+///
+///```
+///use anslatortray::{IncrementalTranslator, Style};
+///
+///let mut translator = IncrementalTranslator::new("Hello world!", Style::WAY);
+///assert_eq!(translator.pig_latin(), "Ellohay orldway!");
+///
+///let mut preview = translator.pig_latin().to_string();
+///
+/////Replace "world" with "there"
+///let edit = translator.edit(6..11, "there");
+///assert_eq!(translator.english(), "Hello there!");
+///assert_eq!(translator.pig_latin(), "Ellohay erethay!");
+///
+/////Only the part of "orldway" that differs from "erethay" changed
+///assert_eq!(edit.replaced, 8..13);
+///assert_eq!(&translator.pig_latin()[edit.pig_latin.clone()], "ereth");
+///
+///preview.replace_range(edit.replaced, &translator.pig_latin()[edit.pig_latin]);
+///assert_eq!(preview, translator.pig_latin());
+///```
+#[derive(Clone, Debug)]
+pub struct IncrementalTranslator {
+    style: Style,
+    english: String,
+    pig_latin: String,
+    spans: Vec<TranslatedSpan>,
+}
+
+///What changed in the translation after an [`IncrementalTranslator::edit()`].
+///
+///To update a copy of the previous translation, replace its `replaced` range with the `pig_latin` range of the new translation.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct TranslationEdit {
+    ///The byte range in the previous translation that was replaced
+    pub replaced: Range<usize>,
+    ///The byte range in the new translation that replaced it
+    pub pig_latin: Range<usize>,
+}
+
+//A span of the English text and where its translation is
+#[derive(Clone, Debug, PartialEq, Eq)]
+struct TranslatedSpan {
+    kind: SpanKind,
+    english: Range<usize>,
+    pig_latin: Range<usize>,
+}
+
+/* Associated Functions and Methods */
+
+impl IncrementalTranslator {
+    ///Translates a document using a [`Style`], remembering its word boundaries for later edits.
+    pub fn new(english: &str, style: Style) -> IncrementalTranslator {
+        let mut translator = IncrementalTranslator { style, english: String::new(), pig_latin: String::new(), spans: Vec::new() };
+        translator.edit(0..0, english);
+        return translator;
+    }
+
+    ///Returns the current English text.
+    pub fn english(&self) -> &str {
+        return &self.english;
+    }
+
+    ///Returns the translation of the current English text.
+    pub fn pig_latin(&self) -> &str {
+        return &self.pig_latin;
+    }
+
+    ///Returns the [`Style`] used for translation.
+    pub fn style(&self) -> &Style {
+        return &self.style;
+    }
+
+    ///Replaces the byte range `range` of the English text with `text`, retranslating only the words the edit touches.
+    ///
+    ///Returns which part of the translation changed. Words on either side of the edit are retranslated too,
+    ///since the edit may join them to (or split them from) the edited text, but only the bytes that differ are reported.
+    ///
+    ///# Panics
+    ///
+    ///Panics if the start or end of `range` is out of bounds or doesn't lie on a [`char`] boundary (like [`String::replace_range()`]).
+    pub fn edit(&mut self, range: Range<usize>, text: &str) -> TranslationEdit {
+        self.english.replace_range(range.clone(), text);
+        let new_end = range.start + text.len();
+
+        //Restart at the last word starting before the edit (or the start of the text): a word's span never depends on what came before it,
+        //and since its first letter wasn't edited, neither does the span before it
+        let first = self.spans.iter().rposition(|span| (span.kind == SpanKind::Word) && (span.english.start < range.start)).unwrap_or(0);
+        let restart = self.spans.get(first).map_or(0, |span| span.english.start);
+        let pig_latin_start = self.spans.get(first).map_or(self.pig_latin.len(), |span| span.pig_latin.start);
+
+        //Scan forward until we are back in step with the old spans after the edit
+        let mut new_spans = Vec::new();
+        let mut pig_latin = Vec::<u8>::new();
+        let mut resync = self.spans.len();
+        let mut old_index = first;
+        let mut after_word = false;
+        for span in Spans::new(&self.english.as_bytes()[restart..], self.style.classes()) {
+            let english = (restart + span.range.start)..(restart + span.range.end);
+
+            if english.start >= new_end {
+                //Old spans after the edit have only moved by the change in length
+                while (old_index < self.spans.len()) && ((self.spans[old_index].english.start < range.end) || (self.spans[old_index].english.start - range.end + new_end < english.start)) {
+                    old_index += 1;
+                }
+
+                //Spans are split the same way from here on if we are at the same place in the same state as before
+                if let Some(old_span) = self.spans.get(old_index) {
+                    let old_after_word = (old_index > 0) && (self.spans[old_index - 1].kind == SpanKind::Word);
+                    if (old_span.english.start - range.end + new_end == english.start) && (old_span.kind == span.kind) && (old_after_word == after_word) {
+                        resync = old_index;
+                        break;
+                    }
+                }
+            }
+
+            let pig_latin_span_start = pig_latin.len();
+            if span.kind == SpanKind::Word {
+                translate_byte_string_with(&self.english.as_bytes()[english.clone()], &self.style, &mut pig_latin);
+            } else {
+                pig_latin.extend_from_slice(&self.english.as_bytes()[english.clone()]);
+            }
+
+            after_word = span.kind == SpanKind::Word;
+            new_spans.push(TranslatedSpan {
+                kind: span.kind,
+                english,
+                pig_latin: (pig_latin_start + pig_latin_span_start)..(pig_latin_start + pig_latin.len()),
+            });
+        }
+
+        //Swap in the retranslated spans, and move the ones after them to their new positions
+        let pig_latin_end = self.spans.get(resync).map_or(self.pig_latin.len(), |span| span.pig_latin.start);
+        let new_pig_latin_end = pig_latin_start + pig_latin.len();
+        let pig_latin = translated_str(&pig_latin);
+        let (prefix_len, suffix_len) = common_affixes(&self.pig_latin[pig_latin_start..pig_latin_end], pig_latin);
+        self.pig_latin.replace_range(pig_latin_start..pig_latin_end, pig_latin);
+
+        for span in &mut self.spans[resync..] {
+            span.english = (span.english.start - range.end + new_end)..(span.english.end - range.end + new_end);
+            span.pig_latin = (span.pig_latin.start - pig_latin_end + new_pig_latin_end)..(span.pig_latin.end - pig_latin_end + new_pig_latin_end);
+        }
+        self.spans.splice(first..resync, new_spans);
+
+        //Only report the part of the retranslated words that actually changed
+        return TranslationEdit {
+            replaced: (pig_latin_start + prefix_len)..(pig_latin_end - suffix_len),
+            pig_latin: (pig_latin_start + prefix_len)..(new_pig_latin_end - suffix_len),
+        };
+    }
+
+    ///Consumes the translator, returning the English text and its translation.
+    pub fn into_strings(self) -> (String, String) {
+        return (self.english, self.pig_latin);
+    }
+}
+
+/* Functions */
+
+//Returns the lengths of the common prefix and suffix of two strings, on char boundaries and never overlapping
+fn common_affixes(old: &str, new: &str) -> (usize, usize) {
+    let max_len = old.len().min(new.len());
+
+    let mut prefix_len = old.bytes().zip(new.bytes()).take_while(|(old_byte, new_byte)| old_byte == new_byte).count();
+    while !old.is_char_boundary(prefix_len) || !new.is_char_boundary(prefix_len) {
+        prefix_len -= 1;
+    }
+
+    let mut suffix_len = old.bytes().rev().zip(new.bytes().rev()).take(max_len - prefix_len).take_while(|(old_byte, new_byte)| old_byte == new_byte).count();
+    while !old.is_char_boundary(old.len() - suffix_len) || !new.is_char_boundary(new.len() - suffix_len) {
+        suffix_len -= 1;
+    }
+
+    return (prefix_len, suffix_len);
+}
+
+/* Tests */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::style::RulesVersion;
+
+    const ENGLISH: &str = "Let's try some edge cases: ISn't THIS COOL? naïve mcDONALD's, bcd 123 Hyphens-are-difficult-aren't-they?' dogs' 'tis";
+    const INSERTIONS: [&str; 8] = ["", "a", "'", " ", "xyz", "'s ", "é", "Hello, world"];
+
+    fn check_edit(translator: &mut IncrementalTranslator, range: Range<usize>, text: &str) {
+        let old_pig_latin = translator.pig_latin().to_string();
+        let edit = translator.edit(range, text);
+
+        assert_eq!(translator.pig_latin(), crate::translate_with(translator.english(), translator.style()), "{:?}", translator.english());
+
+        //Applying the edit to the old translation gives the new one
+        let mut patched = old_pig_latin;
+        patched.replace_range(edit.replaced, &translator.pig_latin()[edit.pig_latin]);
+        assert_eq!(patched, translator.pig_latin());
+
+        //The remembered spans still cover everything
+        let rebuilt: String = translator.spans.iter().map(|span| &translator.pig_latin[span.pig_latin.clone()]).collect();
+        assert_eq!(rebuilt, translator.pig_latin());
+        assert_eq!(translator.spans.last().map_or(0, |span| span.english.end), translator.english().len());
+    }
+
+    #[test]
+    fn test_incremental_translator_edits() {
+        static HYPHENATED: crate::ByteClasses = crate::ByteClasses::ENGLISH.with_joiners(b"-");
+        for style in [Style::WAY, Style::FERB.with_rules(RulesVersion::V0_4), Style::WAY.with_classes(&HYPHENATED)] {
+            //Every insertion and deletion at every position
+            for start in (0..=ENGLISH.len()).filter(|&index| ENGLISH.is_char_boundary(index)) {
+                for end in (start..=ENGLISH.len().min(start + 6)).filter(|&index| ENGLISH.is_char_boundary(index)) {
+                    for text in INSERTIONS {
+                        let mut translator = IncrementalTranslator::new(ENGLISH, style);
+                        check_edit(&mut translator, start..end, text);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_common_affixes() {
+        assert_eq!(common_affixes("", ""), (0, 0));
+        assert_eq!(common_affixes("abc", "abc"), (3, 0));
+        assert_eq!(common_affixes("aaa", "aaaa"), (3, 0));
+        assert_eq!(common_affixes("orldway", "erethay"), (0, 2));
+        //Never splits a char, even when the bytes match
+        assert_eq!(common_affixes("é", "è"), (0, 0));
+        assert_eq!(common_affixes("aé", "aè"), (1, 0));
+    }
+
+    #[test]
+    fn test_incremental_translator_typing() {
+        //Type a document one character at a time, then delete it one character at a time from the front
+        let mut translator = IncrementalTranslator::new("", Style::WAY);
+        for (index, character) in ENGLISH.char_indices() {
+            check_edit(&mut translator, index..index, character.encode_utf8(&mut [0; 4]));
+        }
+        assert_eq!(translator.english(), ENGLISH);
+
+        while let Some(character) = translator.english().chars().next() {
+            check_edit(&mut translator, 0..character.len_utf8(), "");
+        }
+        assert_eq!(translator.into_strings(), (String::new(), String::new()));
+    }
+}
@@ -13,32 +13,51 @@
 //!Try compiling this example code:
 //!
 //!```
+//!# #[cfg(feature = "alloc")] {
 //!use anslatortray::translate;
 //!
 //!//Prints "Ellohay orldway omfray ethay Anslatortray orfay Ustray!"
 //!println!("{}", translate("Hello world from the Translator for Rust!"));
+//!# }
 //!```
 //!
 //!Anslatortray also supports using the "yay" suffix instead in special cases if you prefer that:
 //!
 //!```
+//!# #[cfg(feature = "alloc")] {
 //!use anslatortray::translate_yay;
 //!
 //!//Prints "Utbay Iyay eferpray ethay ayyay-ylestay igpay atinlay!"
 //!println!("{}", translate_yay("But I prefer the yay-style pig latin!"));
+//!# }
 //!```
 //!
 //!It also supports Ferb Latin from Phineas and Ferb:
 //!
 //!```
+//!# #[cfg(feature = "alloc")] {
 //!use anslatortray::translate_ferb;
 //!
 //!//Prints "Erewherb's Erryperb?"
 //!println!("{}", translate_ferb("Where's Perry?"));
+//!# }
 //!```
 //!
 //!If none of these suit your needs, you can also choose your own suffixes with [`translate_with_style()`]
 //!
+//!If you store translated text and need it to stay exactly the same across anslatortray releases, pin a [`RulesVersion`] with [`translate_with()`]:
+//!
+//!```
+//!# #[cfg(feature = "alloc")] {
+//!use anslatortray::{translate_with, Style, RulesVersion};
+//!
+//!let style = Style::WAY.with_rules(RulesVersion::V0_6);
+//!
+//!//Prints "Ellohay orldway!", no matter which heuristics future versions add
+//!println!("{}", translate_with("Hello world!", &style));
+//!# }
+//!```
+//!
 //!If you want even more speed than the regular translation functions bring to the table, check out the [`byte_string`] module.
 //!
 //!# Useful Links
@@ -52,7 +71,22 @@
 //!
 //!# Dependencies
 //!
-//!None other than the standard libraries!
+//!None other than the standard libraries by default!
+//!
+//!The optional `tokio` feature (async streaming adapters) depends on `tokio`, `bytes` and `futures-core`, the optional `rayon` feature depends on `rayon`,
+//!and the optional `regex` feature depends on `regex`.
+//!
+//!# Cargo Features
+//!
+//!* `std` (default): Everything, including the [`std::io`] adapters and [`CachingTranslator`]. Implies `alloc`.
+//!* `alloc`: Everything that returns a [`String`] or appends to a [`Vec`], without needing the rest of `std`.
+//!* Without either, the crate is `no_std` and never allocates: [`byte_string::translate_to_slice()`], [`byte_string::translated_len()`],
+//!  [`PigLatin`] (for use with [`core::fmt`]) and [`Style`] are available, for ex. on embedded devices.
+//!* `tokio`: Async streaming adapters (`AsyncPigLatinReader`, `AsyncPigLatinWriter` and `translate_stream()`).
+//!* `rayon`: Translating large inputs on a thread pool (`translate_parallel()`).
+//!* `regex`: Translating only the parts of a text a regex selects, or everything but them (`translate_scoped()`).
+//!* `safe`: Builds the crate with `#![forbid(unsafe_code)]`, replacing unchecked UTF-8 conversions with checked ones and the SIMD scanner with a scalar one
+//!  (a little slower, especially on text with long runs of non-letters).
 //!
 //!# Anslatortray Code and Documentation Licence
 //!
@@ -65,9 +99,96 @@
 //Only enabled if the relevant Cargo feature is
 #![cfg_attr(feature = "nightly-features-benches", feature(test))]
 
+/* no_std Support */
+
+#![cfg_attr(all(not(feature = "std"), not(test)), no_std)]
+
+/* Safety */
+
+//Only enabled if the relevant Cargo feature is
+#![cfg_attr(feature = "safe", forbid(unsafe_code))]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 /* Imports */
 
 pub mod byte_string;
+#[cfg(feature = "alloc")]
 mod string;
+mod style;
+mod spans;
+mod scan;
+mod classes;
+mod utf8;
+#[cfg(feature = "alloc")]
+mod diagnostics;
+mod word;
+#[cfg(feature = "alloc")]
+mod tokens;
+#[cfg(feature = "alloc")]
+mod offsets;
+#[cfg(feature = "std")]
+mod stream;
+#[cfg(feature = "std")]
+mod cache;
+mod display;
+#[cfg(feature = "alloc")]
+mod ext;
+#[cfg(feature = "alloc")]
+mod batch;
+#[cfg(feature = "alloc")]
+mod incremental;
+#[cfg(feature = "alloc")]
+mod runs;
+#[cfg(feature = "alloc")]
+mod hook;
+#[cfg(feature = "alloc")]
+mod selection;
+mod tokenizer;
+#[cfg(feature = "tokio")]
+mod async_io;
+#[cfg(feature = "rayon")]
+mod parallel;
+#[cfg(feature = "regex")]
+mod scoped;
 
-pub use string::{translate, translate_way, translate_yay, translate_hay, translate_ferb, translate_with_style};
+#[cfg(feature = "alloc")]
+pub use string::{translate, translate_way, translate_yay, translate_hay, translate_ferb, translate_with_style, translate_with, translate_with_diagnostics, translate_cow, translate_cow_with};
+pub use style::{Style, RulesVersion};
+pub use classes::ByteClasses;
+#[cfg(feature = "alloc")]
+pub use diagnostics::{Diagnostic, DiagnosticKind};
+pub use word::{Rule, CaseClass};
+#[cfg(feature = "alloc")]
+pub use word::{explain_word, WordExplanation};
+#[cfg(feature = "alloc")]
+pub use tokens::{translate_tokens, Tokens, Token};
+#[cfg(feature = "alloc")]
+pub use offsets::{translate_with_offsets, OffsetMap, OffsetUnit, SpanMapping};
+#[cfg(feature = "std")]
+pub use stream::{PigLatinWriter, PigLatinReader};
+#[cfg(feature = "std")]
+pub use cache::{CachingTranslator, CacheStats, DEFAULT_CACHE_CAPACITY};
+pub use display::PigLatin;
+#[cfg(feature = "alloc")]
+pub use ext::{PigLatinExt, MakePigLatin};
+#[cfg(feature = "alloc")]
+pub use batch::{translate_batch, translate_batch_with, TranslatedBatch};
+#[cfg(feature = "alloc")]
+pub use incremental::{IncrementalTranslator, TranslationEdit};
+#[cfg(feature = "alloc")]
+pub use runs::{translate_runs, translate_runs_with};
+#[cfg(feature = "alloc")]
+pub use hook::{translate_with_hook, WordInfo, Decision};
+#[cfg(feature = "alloc")]
+pub use selection::{translate_selected, translate_selected_with, Selection};
+pub use tokenizer::{Tokenizer, Segment, DefaultTokenizer, DefaultSegments, WordCharTokenizer, WordCharSegments};
+#[cfg(feature = "alloc")]
+pub use tokenizer::translate_with_tokenizer;
+#[cfg(feature = "tokio")]
+pub use async_io::{AsyncPigLatinReader, AsyncPigLatinWriter, PigLatinStream, translate_stream};
+#[cfg(feature = "rayon")]
+pub use parallel::{translate_parallel, translate_parallel_with_chunk_len, ChunkTiming, DEFAULT_PARALLEL_CHUNK_LEN};
+#[cfg(feature = "regex")]
+pub use scoped::{translate_scoped, translate_scoped_with, RegexScope};
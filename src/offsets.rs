@@ -0,0 +1,259 @@
+/* offsets.rs
+ * By: John Jekel
+ * Copyright (C) 2022 John Jekel
+ * See the LICENSE file at the root of the project for licensing info.
+ *
+ * Maps positions in English text to positions in its translation and back
+ *
+*/
+
+/* Imports */
+
+use core::ops::Range;
+
+use alloc::string::String;
+use alloc::vec;
+use alloc::vec::Vec;
+
+use crate::byte_string::translate_with as translate_byte_string_with;
+use crate::spans::{Spans, SpanKind};
+use crate::style::Style;
+use crate::utf8::{translated_str, translated_string};
+
+/* Types */
+
+///The unit an offset into a string is measured in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum OffsetUnit {
+    ///UTF-8 bytes (what Rust uses to index [`str`])
+    Byte,
+    ///Unicode scalar values (what [`str::chars()`] yields)
+    Char,
+    ///UTF-16 code units (what JavaScript, Java and many editors use)
+    Utf16,
+}
+
+///How one piece of English text lines up with its translation; see [`OffsetMap::spans()`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SpanMapping {
+    ///The byte range in the English text
+    pub english: Range<usize>,
+    ///The byte range in the Pig Latin text
+    pub pig_latin: Range<usize>,
+    ///Whether this is a translated word (otherwise the text was copied as-is)
+    pub translated: bool,
+}
+
+///Maps offsets between English text and its translation, at word granularity; see [`translate_with_offsets()`].
+///
+///Offsets in text that was copied as-is (whitespace, punctuation, contraction suffixes, etc.) map exactly.
+///Since a word is rearranged when it is translated, an offset inside a word maps to the start of the translated (or original) word instead.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct OffsetMap {
+    spans: Vec<SpanMapping>,
+    //The position of the start of each span, plus one more for the end of the text
+    english_positions: Vec<Position>,
+    pig_latin_positions: Vec<Position>,
+}
+
+//An offset measured in every OffsetUnit at once
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+struct Position {
+    byte: usize,
+    char: usize,
+    utf16: usize,
+}
+
+//Which way we are mapping offsets
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Direction {
+    ToPigLatin,
+    ToEnglish,
+}
+
+/* Associated Functions and Methods */
+
+impl OffsetMap {
+    ///Returns how every piece of the English text lines up with the translation, in order.
+    pub fn spans(&self) -> &[SpanMapping] {
+        return &self.spans;
+    }
+
+    ///Maps an offset in the English text to the corresponding offset in the translation (measured in the same unit).
+    ///
+    ///Offsets inside a word map to the start of the translated word. Offsets past the end map to the end of the translation.
+    pub fn to_pig_latin(&self, english_offset: usize, unit: OffsetUnit) -> usize {
+        return self.map(english_offset, unit, Direction::ToPigLatin, false);
+    }
+
+    ///Maps an offset in the translation to the corresponding offset in the English text (measured in the same unit).
+    ///
+    ///Offsets inside a translated word map to the start of the original word. Offsets past the end map to the end of the English text.
+    pub fn to_english(&self, pig_latin_offset: usize, unit: OffsetUnit) -> usize {
+        return self.map(pig_latin_offset, unit, Direction::ToEnglish, false);
+    }
+
+    ///Maps a range in the English text (ex. a search hit or annotation) to the corresponding range in the translation.
+    ///
+    ///The range is widened to cover any words it only partially overlaps.
+    pub fn range_to_pig_latin(&self, english_range: Range<usize>, unit: OffsetUnit) -> Range<usize> {
+        return self.map(english_range.start, unit, Direction::ToPigLatin, false)..self.map(english_range.end, unit, Direction::ToPigLatin, true);
+    }
+
+    ///Maps a range in the translation to the corresponding range in the English text.
+    ///
+    ///The range is widened to cover any words it only partially overlaps.
+    pub fn range_to_english(&self, pig_latin_range: Range<usize>, unit: OffsetUnit) -> Range<usize> {
+        return self.map(pig_latin_range.start, unit, Direction::ToEnglish, false)..self.map(pig_latin_range.end, unit, Direction::ToEnglish, true);
+    }
+
+    fn map(&self, offset: usize, unit: OffsetUnit, direction: Direction, round_up: bool) -> usize {
+        let (from, to) = match direction {
+            Direction::ToPigLatin => (&self.english_positions, &self.pig_latin_positions),
+            Direction::ToEnglish => (&self.pig_latin_positions, &self.english_positions),
+        };
+
+        //Find the last span starting at or before the offset
+        let index = from.partition_point(|position| position.get(unit) <= offset) - 1;
+        if index == self.spans.len() {//At or past the end of the text
+            return to[index].get(unit);
+        }
+
+        let into_span = offset - from[index].get(unit);
+        if into_span == 0 {
+            return to[index].get(unit);
+        } else if !self.spans[index].translated {//The text is the same on both sides
+            return to[index].get(unit) + into_span;
+        } else if round_up {
+            return to[index + 1].get(unit);
+        } else {
+            return to[index].get(unit);
+        }
+    }
+}
+
+impl Position {
+    fn get(&self, unit: OffsetUnit) -> usize {
+        return match unit {
+            OffsetUnit::Byte => self.byte,
+            OffsetUnit::Char => self.char,
+            OffsetUnit::Utf16 => self.utf16,
+        };
+    }
+
+    fn advanced_by(&self, text: &str) -> Position {
+        return Position {
+            byte: self.byte + text.len(),
+            char: self.char + text.chars().count(),
+            utf16: self.utf16 + text.encode_utf16().count(),
+        };
+    }
+}
+
+/* Functions */
+
+///Translates a multi-word string (including punctuation) like [`crate::translate_with()`], and also returns an [`OffsetMap`] between the two!
+///
+///Since every word grows by a different amount when translated, this lets you carry a cursor, selection, search hit or annotation
+///from the English text over to the translation (or back), in bytes, chars or UTF-16 code units.
+///
+///# Examples
+///
+///```
+///use anslatortray::{translate_with_offsets, Style, OffsetUnit};
+///
+///let english = "Hello there, wörld!";
+///let (pig_latin, offsets) = translate_with_offsets(english, &Style::WAY);
+///assert_eq!(pig_latin, "Ellohay erethay, wwayörlday!");
+///
+/////The comma is copied as-is, so it maps exactly
+///assert_eq!(offsets.to_pig_latin(11, OffsetUnit::Byte), 15);
+///assert_eq!(&pig_latin[15..16], ",");
+///
+/////An offset inside a word maps to the start of the translated word
+///assert_eq!(offsets.to_pig_latin(8, OffsetUnit::Byte), 8);
+///
+/////A range covering part of a word is widened to the whole translated word
+///assert_eq!(offsets.range_to_pig_latin(7..9, OffsetUnit::Byte), 8..15);
+///assert_eq!(offsets.range_to_english(8..15, OffsetUnit::Byte), 6..11);
+///
+/////The "ö" is two bytes, but only one char and one UTF-16 code unit
+///assert_eq!(offsets.to_pig_latin(18, OffsetUnit::Char), 27);
+///assert_eq!(offsets.to_english(27, OffsetUnit::Utf16), 18);
+///```
+pub fn translate_with_offsets(english: &str, style: &Style) -> (String, OffsetMap) {
+    let mut pig_latin_string_bytes = Vec::<u8>::with_capacity(english.len() * 2);//Plenty of headroom in case the words are very small or the suffixes are long
+    let mut spans = Vec::new();
+    let mut english_positions = vec![Position::default()];
+    let mut pig_latin_positions = vec![Position::default()];
+
+    for span in Spans::new(english.as_bytes(), style.classes()) {
+        let pig_latin_start = pig_latin_string_bytes.len();
+        let translated = span.kind == SpanKind::Word;
+        if translated {
+            translate_byte_string_with(&english.as_bytes()[span.range.clone()], style, &mut pig_latin_string_bytes);
+        } else {
+            pig_latin_string_bytes.extend_from_slice(&english.as_bytes()[span.range.clone()]);
+        }
+
+        //Spans always start and end next to ASCII letters (or the ends of the string), so they lie on char boundaries
+        let english_text = &english[span.range.clone()];
+        //Only complete spans (separators from a &str, or ASCII words with suffixes from &strs) are ever appended
+        let pig_latin_text = translated_str(&pig_latin_string_bytes[pig_latin_start..]);
+
+        english_positions.push(english_positions.last().unwrap().advanced_by(english_text));
+        pig_latin_positions.push(pig_latin_positions.last().unwrap().advanced_by(pig_latin_text));
+        spans.push(SpanMapping { english: span.range, pig_latin: pig_latin_start..pig_latin_string_bytes.len(), translated });
+    }
+
+    let pig_latin = translated_string(pig_latin_string_bytes);
+    return (pig_latin, OffsetMap { spans, english_positions, pig_latin_positions });
+}
+
+/* Tests */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_offset_map_round_trips_separators() {
+        const ENGLISH: &str = "¡Hola, señor! 🐷 isn't it?";
+        let (pig_latin, offsets) = translate_with_offsets(ENGLISH, &Style::WAY);
+        assert_eq!(pig_latin, crate::translate(ENGLISH));
+
+        for mapping in offsets.spans() {
+            assert_eq!(mapping.translated, ENGLISH[mapping.english.clone()] != pig_latin[mapping.pig_latin.clone()]);
+            if !mapping.translated {
+                for offset in mapping.english.clone() {
+                    let mapped = offsets.to_pig_latin(offset, OffsetUnit::Byte);
+                    assert_eq!(offsets.to_english(mapped, OffsetUnit::Byte), offset);
+                }
+            }
+        }
+
+        //Every unit agrees on where the pig emoji ends up
+        let pig_byte = ENGLISH.find('🐷').unwrap();
+        let pig_char = ENGLISH[..pig_byte].chars().count();
+        let pig_utf16 = ENGLISH[..pig_byte].encode_utf16().count();
+        let mapped_byte = offsets.to_pig_latin(pig_byte, OffsetUnit::Byte);
+        assert_eq!(&pig_latin[mapped_byte..(mapped_byte + 4)], "🐷");
+        assert_eq!(offsets.to_pig_latin(pig_char, OffsetUnit::Char), pig_latin[..mapped_byte].chars().count());
+        assert_eq!(offsets.to_pig_latin(pig_utf16, OffsetUnit::Utf16), pig_latin[..mapped_byte].encode_utf16().count());
+    }
+
+    #[test]
+    fn test_offset_map_ends() {
+        let (pig_latin, offsets) = translate_with_offsets("pig", &Style::WAY);
+        assert_eq!(offsets.to_pig_latin(0, OffsetUnit::Byte), 0);
+        assert_eq!(offsets.to_pig_latin(1, OffsetUnit::Byte), 0);
+        assert_eq!(offsets.to_pig_latin(3, OffsetUnit::Byte), pig_latin.len());
+        assert_eq!(offsets.to_pig_latin(100, OffsetUnit::Byte), pig_latin.len());
+        assert_eq!(offsets.range_to_english(1..2, OffsetUnit::Char), 0..3);
+
+        let (pig_latin, offsets) = translate_with_offsets("", &Style::WAY);
+        assert_eq!(pig_latin, "");
+        assert_eq!(offsets.spans(), []);
+        assert_eq!(offsets.to_english(0, OffsetUnit::Utf16), 0);
+    }
+}
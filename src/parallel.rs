@@ -0,0 +1,160 @@
+/* parallel.rs
+ * By: John Jekel
+ * Copyright (C) 2022 John Jekel
+ * See the LICENSE file at the root of the project for licensing info.
+ *
+ * Translation of large inputs split into chunks on rayon's thread pool
+ *
+*/
+
+/* Imports */
+
+use std::ops::Range;
+use std::time::{Duration, Instant};
+
+use rayon::prelude::*;
+
+use crate::byte_string::translate_with as translate_byte_string_with;
+use crate::style::Style;
+
+/* Constants */
+
+///The approximate size (in bytes) of each chunk [`translate_parallel()`] splits its input into.
+pub const DEFAULT_PARALLEL_CHUNK_LEN: usize = 64 * 1024;
+
+/* Types */
+
+///How long one chunk of a parallel translation took; see [`translate_parallel()`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ChunkTiming {
+    ///The byte range of the chunk in the English text
+    pub english: Range<usize>,
+    ///The byte range of the chunk's translation in the Pig Latin text (relative to where the translation was appended)
+    pub pig_latin: Range<usize>,
+    ///How long translating the chunk took
+    pub duration: Duration,
+}
+
+/* Functions */
+
+///Translates a large multi-word byte-string (including punctuation) using a [`Style`] on rayon's thread pool, returning how long each chunk took!
+///
+///The input is split into chunks of roughly [`DEFAULT_PARALLEL_CHUNK_LEN`] bytes at whitespace (which never falls inside a word or contraction),
+///so the result is byte-identical to [`crate::byte_string::translate_with()`]. Inputs smaller than one chunk are translated on the current thread.
+///
+///Note: The resulting translation is appended to the provided buffer, so one may wish to ensure it is cleared before each use or not depending on the application.
+///
+///Requires the `rayon` feature.
+///
+///# Examples
+///
+///```
+///use anslatortray::{translate_parallel, Style};
+///
+///let english = "Hello world! ".repeat(100_000);
+///let mut pig_latin = Vec::<u8>::new();
+///let timings = translate_parallel(english.as_bytes(), &Style::WAY, &mut pig_latin);
+///
+///assert_eq!(pig_latin, "Ellohay orldway! ".repeat(100_000).as_bytes());
+///assert!(timings.len() > 1);
+///assert_eq!(timings.last().unwrap().english.end, english.len());
+///```
+pub fn translate_parallel(english: &[u8], style: &Style, pig_latin_string: &mut Vec<u8>) -> Vec<ChunkTiming> {
+    return translate_parallel_with_chunk_len(english, style, DEFAULT_PARALLEL_CHUNK_LEN, pig_latin_string);
+}
+
+///Like [`translate_parallel()`], but splitting the input into chunks of roughly `chunk_len` bytes.
+///
+///Requires the `rayon` feature.
+///
+///# Panics
+///
+///Panics if `chunk_len` is zero.
+pub fn translate_parallel_with_chunk_len(english: &[u8], style: &Style, chunk_len: usize, pig_latin_string: &mut Vec<u8>) -> Vec<ChunkTiming> {
+    assert!(chunk_len > 0, "chunk_len must be greater than zero");
+
+    let chunks = split_at_whitespace(english, chunk_len);
+    let translated_chunks: Vec<(Vec<u8>, Duration)> = chunks.par_iter().map(|chunk| {
+        let start_time = Instant::now();
+        let mut pig_latin_chunk = Vec::<u8>::with_capacity(chunk.len() * 2);//Plenty of headroom in case the words are very small or the suffixes are long
+        translate_byte_string_with(&english[chunk.clone()], style, &mut pig_latin_chunk);
+        return (pig_latin_chunk, start_time.elapsed());
+    }).collect();
+
+    //Join the chunks back together in order
+    let mut timings = Vec::with_capacity(chunks.len());
+    let start_of_translation = pig_latin_string.len();
+    pig_latin_string.reserve(translated_chunks.iter().map(|(pig_latin_chunk, _)| pig_latin_chunk.len()).sum());
+    for (chunk, (pig_latin_chunk, duration)) in chunks.into_iter().zip(translated_chunks) {
+        let pig_latin_start = pig_latin_string.len() - start_of_translation;
+        pig_latin_string.extend_from_slice(&pig_latin_chunk);
+        timings.push(ChunkTiming { english: chunk, pig_latin: pig_latin_start..(pig_latin_string.len() - start_of_translation), duration });
+    }
+
+    return timings;
+}
+
+//Splits english into ranges of at least chunk_len bytes (except the last), each ending just before ASCII whitespace
+//Words and contraction suffixes never contain whitespace, so every chunk translates the same on its own as it does in context
+fn split_at_whitespace(english: &[u8], chunk_len: usize) -> Vec<Range<usize>> {
+    let mut chunks = Vec::new();
+    let mut start: usize = 0;
+    while start < english.len() {
+        let target = start.saturating_add(chunk_len);
+        let end = if target >= english.len() {
+            english.len()
+        } else {
+            match english[target..].iter().position(|x| x.is_ascii_whitespace()) {
+                Some(offset) => target + offset,
+                None => english.len(),
+            }
+        };
+
+        chunks.push(start..end);
+        start = end;
+    }
+    return chunks;
+}
+
+/* Tests */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::style::RulesVersion;
+
+    const ENGLISH: &str = "Let's try some edge cases: ISn't THIS COOL? naïve mcDONALD's, bcd 123\nHyphens-are-difficult-aren't-they?' \t dogs' 'tis Ok";
+
+    #[test]
+    fn test_translate_parallel_matches_translate_with() {
+        static HYPHENATED: crate::ByteClasses = crate::ByteClasses::ENGLISH.with_joiners(b"-");
+        for style in [Style::WAY, Style::FERB.with_rules(RulesVersion::V0_4), Style::WAY.with_classes(&HYPHENATED)] {
+            let mut expected = Vec::new();
+            translate_byte_string_with(ENGLISH.as_bytes(), &style, &mut expected);
+
+            for chunk_len in 1..=ENGLISH.len() + 1 {
+                let mut pig_latin = b"> ".to_vec();
+                let timings = translate_parallel_with_chunk_len(ENGLISH.as_bytes(), &style, chunk_len, &mut pig_latin);
+                assert_eq!(&pig_latin[2..], expected, "chunk length {}", chunk_len);
+
+                //The chunks cover everything in order
+                let mut english_end = 0;
+                let mut pig_latin_end = 0;
+                for timing in &timings {
+                    assert_eq!((timing.english.start, timing.pig_latin.start), (english_end, pig_latin_end));
+                    english_end = timing.english.end;
+                    pig_latin_end = timing.pig_latin.end;
+                }
+                assert_eq!((english_end, pig_latin_end), (ENGLISH.len(), expected.len()));
+            }
+        }
+    }
+
+    #[test]
+    fn test_split_at_whitespace() {
+        assert_eq!(split_at_whitespace(b"", 4), [] as [Range<usize>; 0]);
+        assert_eq!(split_at_whitespace(b"ab cdef gh", 1), [0..2, 2..7, 7..10]);
+        assert_eq!(split_at_whitespace(b"ab cdef gh", 4), [0..7, 7..10]);
+        assert_eq!(split_at_whitespace(b"abcdefgh", 4), vec![0..8]);
+    }
+}
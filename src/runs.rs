@@ -0,0 +1,156 @@
+/* runs.rs
+ * By: John Jekel
+ * Copyright (C) 2022 John Jekel
+ * See the LICENSE file at the root of the project for licensing info.
+ *
+ * Translation of rich text split into styled runs, keeping words that cross run boundaries whole
+ *
+*/
+
+/* Imports */
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::byte_string::{analyze_word, translate_with as translate_byte_string_with};
+use crate::spans::{Spans, SpanKind};
+use crate::style::Style;
+use crate::utf8::translated_str;
+use crate::word::Rule;
+
+/* Functions */
+
+///Translates rich text made of `(text, id)` runs (ex. `<b>Hel</b>lo`, or terminal output in different colours), keeping words that cross runs whole!
+///
+///The runs are translated as if they were one string (like [`crate::translate()`]), so "Hel" and "lo" become "Ellohay" rather than "Elhay olay".
+///Every letter of the translation keeps the id of the run it came from, the suffix of a word takes the id of the letter just before it,
+///and everything else (whitespace, punctuation, etc.) stays in its run. Neighbouring characters from the same run are grouped back together,
+///so a run may be split in two if letters from another run move into the middle of it. Empty runs are dropped.
+///
+///The id can be anything that describes the run (a style index, a colour, a tag name, etc.); it is only ever cloned.
+///
+///# Examples
+///
+///```
+///use anslatortray::translate_runs;
+///
+/////<b>Hel</b>lo world
+///let runs = translate_runs(&[("Hel", "b"), ("lo world", "")]);
+///assert_eq!(runs, [
+///    ("El".to_string(), "b"),
+///    ("lo".to_string(), ""),
+///    ("hay".to_string(), "b"),
+///    (" orldway".to_string(), ""),
+///]);
+///```
+pub fn translate_runs<Id: Clone>(runs: &[(&str, Id)]) -> Vec<(String, Id)> {
+    return translate_runs_with(runs, &Style::WAY);
+}
+
+///Translates rich text made of `(text, id)` runs using a [`Style`], keeping words that cross runs whole; see [`translate_runs()`].
+///
+///# Examples
+///
+///```
+///use anslatortray::{translate_runs_with, Style};
+///
+///#[derive(Clone, Debug, PartialEq)]
+///enum Colour { Red, Blue }
+///
+///let runs = translate_runs_with(&[("Wh", Colour::Red), ("ere's Perry?", Colour::Blue)], &Style::FERB);
+///assert_eq!(runs, [
+///    ("Ere".to_string(), Colour::Blue),
+///    ("wherb".to_string(), Colour::Red),
+///    ("'s Erryperb?".to_string(), Colour::Blue),
+///]);
+///```
+pub fn translate_runs_with<Id: Clone>(runs: &[(&str, Id)], style: &Style) -> Vec<(String, Id)> {
+    //Translate everything as one string, remembering which run each byte of the translation came from
+    let english: String = runs.iter().map(|(text, _)| *text).collect();
+    let english_runs: Vec<usize> = runs.iter().enumerate().flat_map(|(index, (text, _))| core::iter::repeat_n(index, text.len())).collect();
+
+    let mut pig_latin = Vec::<u8>::with_capacity(english.len() * 2);//Plenty of headroom in case the words are very small or the suffixes are long
+    let mut pig_latin_runs = Vec::<usize>::with_capacity(english.len() * 2);
+    for span in Spans::new(english.as_bytes(), style.classes()) {
+        let text = &english.as_bytes()[span.range.clone()];
+        let text_runs = &english_runs[span.range];
+        if span.kind != SpanKind::Word {
+            pig_latin.extend_from_slice(text);
+            pig_latin_runs.extend_from_slice(text_runs);
+            continue;
+        }
+
+        //The translation of a word is its letters (rotated if the onset moves to the end), followed by the suffix
+        let analysis = analyze_word(text, style.word_rules());
+        let rotation = if analysis.rule == Rule::ConsonantCluster { analysis.onset_len } else { 0 };
+        translate_byte_string_with(text, style, &mut pig_latin);
+        pig_latin_runs.extend((0..text.len()).map(|index| text_runs[(index + rotation) % text.len()]));
+
+        let suffix_run = *pig_latin_runs.last().unwrap();
+        pig_latin_runs.resize(pig_latin.len(), suffix_run);
+    }
+
+    //Group neighbouring bytes from the same run back together (words are ASCII and everything else is copied whole, so this never splits a char)
+    let pig_latin = translated_str(&pig_latin);
+    let mut translated_runs = Vec::<(String, Id)>::new();
+    let mut start: usize = 0;
+    while start < pig_latin.len() {
+        let run = pig_latin_runs[start];
+        let len = pig_latin_runs[start..].iter().position(|&other_run| other_run != run).unwrap_or(pig_latin.len() - start);
+        translated_runs.push((String::from(&pig_latin[start..(start + len)]), runs[run].1.clone()));
+        start += len;
+    }
+    return translated_runs;
+}
+
+/* Tests */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::style::RulesVersion;
+
+    const ENGLISH: &str = "Let's try some edge cases: ISn't THIS COOL? naïve mcDONALD's, bcd 123 Hyphens-are-difficult-aren't-they?' A";
+
+    #[test]
+    fn test_translate_runs_matches_translate_with() {
+        static HYPHENATED: crate::ByteClasses = crate::ByteClasses::ENGLISH.with_joiners(b"-");
+        for style in [Style::WAY, Style::FERB, Style::new("äy", "wäy"), Style::HAY.with_rules(RulesVersion::V0_4), Style::WAY.with_classes(&HYPHENATED)] {
+            let expected = crate::translate_with(ENGLISH, &style);
+
+            //Split the text into runs of every length
+            for run_len in 1..ENGLISH.len() {
+                let mut runs = Vec::new();
+                let mut start = 0;
+                while start < ENGLISH.len() {
+                    let mut end = (start + run_len).min(ENGLISH.len());
+                    while !ENGLISH.is_char_boundary(end) {
+                        end += 1;
+                    }
+                    runs.push((&ENGLISH[start..end], runs.len()));
+                    start = end;
+                }
+
+                let translated = translate_runs_with(&runs, &style);
+                assert_eq!(translated.iter().map(|(text, _)| text.as_str()).collect::<String>(), expected, "run length {}", run_len);
+                assert!(translated.iter().all(|(text, _)| !text.is_empty()));
+                assert!(translated.windows(2).all(|pair| pair[0].1 != pair[1].1));
+            }
+        }
+    }
+
+    #[test]
+    fn test_translate_runs_keeps_letters_in_their_runs() {
+        //Each letter of "String" in its own run
+        let runs: Vec<(&str, usize)> = "String".char_indices().map(|(index, _)| (&"String"[index..(index + 1)], index)).collect();
+        let translated = translate_runs(&runs);
+        assert_eq!(translated, [
+            ("I".to_string(), 3), ("n".to_string(), 4), ("g".to_string(), 5),
+            ("s".to_string(), 0), ("t".to_string(), 1), ("ray".to_string(), 2),
+        ]);
+
+        //Separators and empty runs
+        assert_eq!(translate_runs(&[("", 0), ("Hi", 1), ("", 2), (", you", 3)]), [("Ihay".to_string(), 1), (", ouyay".to_string(), 3)]);
+        assert_eq!(translate_runs::<u8>(&[]), []);
+    }
+}
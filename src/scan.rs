@@ -0,0 +1,262 @@
+/* scan.rs
+ * By: John Jekel
+ * Copyright (C) 2022 John Jekel
+ * See the LICENSE file at the root of the project for licensing info.
+ *
+ * Vectorized scanning for the next letter or the end of a word (SSE2/AVX2 on x86_64, NEON on aarch64, or a scalar fallback)
+ * The vectorized versions need unsafe intrinsics, so the safe feature always uses the scalar fallback
+ *
+*/
+
+/* Imports */
+
+use crate::classes::ByteClasses;
+
+/* Constants */
+
+//Most separators and words in prose are short, so check this many bytes one at a time before bothering with vectors
+const SCALAR_PREFIX_LEN: usize = 8;
+//Shorter slices than this (after the prefix) are scanned one byte at a time, since loading a vector isn't worth it
+const MIN_VECTOR_LEN: usize = 16;
+
+/* Functions */
+
+//Returns the index of the first letter (according to classes) in haystack, if any
+#[inline(always)]
+pub(crate) fn find_letter(haystack: &[u8], classes: &ByteClasses) -> Option<usize> {
+    return find(haystack, classes, true);
+}
+
+//Returns the index of the first byte in haystack that isn't a letter or a joiner between letters (according to classes), if any
+//The haystack must directly follow a letter (or start with one), so a joiner at its very start is between letters if the byte after it is a letter
+#[inline(always)]
+pub(crate) fn find_non_letter(haystack: &[u8], classes: &ByteClasses) -> Option<usize> {
+    if classes.has_joiners() {//TODO annotate this branch as unlikely taken
+        return find_end_of_joined_word(haystack, classes);
+    }
+    return find(haystack, classes, false);
+}
+
+//The vectorized versions hardcode the letter class, which is fine since it is always exactly a-z and A-Z
+//(joiners are handled by find_end_of_joined_word instead)
+#[inline(always)]
+fn find(haystack: &[u8], classes: &ByteClasses, letter: bool) -> Option<usize> {
+    let prefix_len = haystack.len().min(SCALAR_PREFIX_LEN);
+    if let Some(index) = haystack[..prefix_len].iter().position(|&x| classes.is_letter(x) == letter) {
+        return Some(index);
+    }
+
+    let rest = &haystack[prefix_len..];
+    if rest.len() >= MIN_VECTOR_LEN {
+        #[cfg(all(target_arch = "x86_64", not(feature = "safe")))]
+        {
+            let found = if x86_64::avx2_available() {
+                //This is safe since we just checked that the CPU supports AVX2
+                unsafe { x86_64::find_avx2(rest, classes, letter) }
+            } else {
+                //This is safe since SSE2 is always available on x86_64
+                unsafe { x86_64::find_sse2(rest, classes, letter) }
+            };
+            return found.map(|index| prefix_len + index);
+        }
+
+        #[cfg(all(target_arch = "aarch64", target_feature = "neon", not(feature = "safe")))]
+        {
+            //This is safe since we just checked that NEON is enabled at compile time
+            return unsafe { aarch64::find_neon(rest, classes, letter) }.map(|index| prefix_len + index);
+        }
+    }
+
+    return find_scalar(haystack, classes, letter, prefix_len);
+}
+
+//Scans one byte at a time, starting at start
+#[inline(always)]
+fn find_scalar(haystack: &[u8], classes: &ByteClasses, letter: bool, start: usize) -> Option<usize> {
+    return haystack[start..].iter().position(|&x| classes.is_letter(x) == letter).map(|index| start + index);
+}
+
+//Scans one byte at a time for the end of a word that may contain joiners
+#[inline(always)]
+fn find_end_of_joined_word(haystack: &[u8], classes: &ByteClasses) -> Option<usize> {
+    let mut index: usize = 0;
+    while index < haystack.len() {
+        let byte = haystack[index];
+        if !classes.is_letter(byte) {
+            //Since we stop at the first byte that doesn't continue the word, the byte before a joiner is always a letter
+            let joined = classes.is_joiner(byte) && (index + 1 < haystack.len()) && classes.is_letter(haystack[index + 1]);
+            if !joined {
+                return Some(index);
+            }
+        }
+        index += 1;
+    }
+
+    return None;
+}
+
+/* Architecture-Specific Implementations */
+
+#[cfg(all(target_arch = "x86_64", not(feature = "safe")))]
+mod x86_64 {
+    use core::arch::x86_64::*;
+    use super::{find_scalar, ByteClasses};
+
+    //Detected once at runtime if we have std, otherwise decided at compile time
+    #[inline(always)]
+    pub(super) fn avx2_available() -> bool {
+        #[cfg(feature = "std")]
+        {
+            return std::is_x86_feature_detected!("avx2");
+        }
+        #[cfg(not(feature = "std"))]
+        {
+            return cfg!(target_feature = "avx2");
+        }
+    }
+
+    //A byte is a letter iff (byte | 0x20) is in 'a'..='z'. SSE2/AVX2 only have signed comparisons, so we shift
+    //that range to the very bottom of the signed range (-128..=-103) and check if the result is less than -102
+    const SHIFT: i8 = (128 - b'a' as i16) as i8;
+    const LIMIT: i8 = (-128 + 26) as i8;
+
+    #[target_feature(enable = "sse2")]
+    pub(super) unsafe fn find_sse2(haystack: &[u8], classes: &ByteClasses, letter: bool) -> Option<usize> {
+        let mut index: usize = 0;
+        while index + 16 <= haystack.len() {
+            //This is safe since we just checked there are at least 16 bytes left (and loadu doesn't need alignment)
+            let bytes = unsafe { _mm_loadu_si128(haystack.as_ptr().add(index) as *const __m128i) };
+            let shifted = _mm_add_epi8(_mm_or_si128(bytes, _mm_set1_epi8(0x20)), _mm_set1_epi8(SHIFT));
+            let letters = _mm_movemask_epi8(_mm_cmplt_epi8(shifted, _mm_set1_epi8(LIMIT))) as u32;
+
+            let matches = if letter { letters } else { !letters & 0xFFFF };
+            if matches != 0 {
+                return Some(index + matches.trailing_zeros() as usize);
+            }
+            index += 16;
+        }
+
+        return find_scalar(haystack, classes, letter, index);
+    }
+
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn find_avx2(haystack: &[u8], classes: &ByteClasses, letter: bool) -> Option<usize> {
+        let mut index: usize = 0;
+        while index + 32 <= haystack.len() {
+            //This is safe since we just checked there are at least 32 bytes left (and loadu doesn't need alignment)
+            let bytes = unsafe { _mm256_loadu_si256(haystack.as_ptr().add(index) as *const __m256i) };
+            let shifted = _mm256_add_epi8(_mm256_or_si256(bytes, _mm256_set1_epi8(0x20)), _mm256_set1_epi8(SHIFT));
+            let letters = _mm256_movemask_epi8(_mm256_cmpgt_epi8(_mm256_set1_epi8(LIMIT), shifted)) as u32;
+
+            let matches = if letter { letters } else { !letters };
+            if matches != 0 {
+                return Some(index + matches.trailing_zeros() as usize);
+            }
+            index += 32;
+        }
+
+        //Finish off any remaining 16-byte block with SSE2 (which every AVX2 CPU has)
+        return unsafe { find_sse2(&haystack[index..], classes, letter) }.map(|offset| index + offset);
+    }
+}
+
+#[cfg(all(target_arch = "aarch64", target_feature = "neon", not(feature = "safe")))]
+mod aarch64 {
+    use core::arch::aarch64::*;
+    use super::{find_scalar, ByteClasses};
+
+    #[target_feature(enable = "neon")]
+    pub(super) unsafe fn find_neon(haystack: &[u8], classes: &ByteClasses, letter: bool) -> Option<usize> {
+        let mut index: usize = 0;
+        while index + 16 <= haystack.len() {
+            //This is safe since we just checked there are at least 16 bytes left
+            let bytes = unsafe { vld1q_u8(haystack.as_ptr().add(index)) };
+            //A byte is a letter iff (byte | 0x20) - 'a' is less than 26 (unsigned)
+            let offset = vsubq_u8(vorrq_u8(bytes, vdupq_n_u8(0x20)), vdupq_n_u8(b'a'));
+            let mut matches = vcltq_u8(offset, vdupq_n_u8(26));
+            if !letter {
+                matches = vmvnq_u8(matches);
+            }
+
+            //NEON has no movemask, so narrow each byte to 4 bits instead
+            let nibbles = vget_lane_u64::<0>(vreinterpret_u64_u8(vshrn_n_u16::<4>(vreinterpretq_u16_u8(matches))));
+            if nibbles != 0 {
+                return Some(index + (nibbles.trailing_zeros() / 4) as usize);
+            }
+            index += 16;
+        }
+
+        return find_scalar(haystack, classes, letter, index);
+    }
+}
+
+/* Tests */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ENGLISH: &ByteClasses = &ByteClasses::ENGLISH;
+
+    #[test]
+    fn test_find_matches_scalar() {
+        //Every byte value at every position of every length, so every vector width and tail gets exercised
+        for len in 0..80 {
+            for position in 0..len {
+                for byte in 0..=255u8 {
+                    let mut haystack = std::vec![b' '; len];
+                    haystack[position] = byte;
+                    assert_eq!(find_letter(&haystack, ENGLISH), find_scalar(&haystack, ENGLISH, true, 0), "{} {} {}", len, position, byte);
+
+                    let mut haystack = std::vec![b'a'; len];
+                    haystack[position] = byte;
+                    assert_eq!(find_non_letter(&haystack, ENGLISH), find_scalar(&haystack, ENGLISH, false, 0), "{} {} {}", len, position, byte);
+                }
+            }
+        }
+    }
+
+    #[test]
+    #[cfg(all(target_arch = "x86_64", not(feature = "safe")))]
+    fn test_find_sse2_matches_scalar() {
+        //find() prefers AVX2 when available, so make sure the SSE2 path is tested on its own too
+        for len in 0..48 {
+            for position in 0..len {
+                for byte in [0u8, b'\'', b'@', b'A', b'Z', b'[', b'`', b'a', b'z', b'{', 0x80, 0xC3, 0xFF] {
+                    let mut haystack = std::vec![b' '; len];
+                    haystack[position] = byte;
+                    //This is safe since SSE2 is always available on x86_64
+                    assert_eq!(unsafe { x86_64::find_sse2(&haystack, ENGLISH, true) }, find_scalar(&haystack, ENGLISH, true, 0));
+                    assert_eq!(unsafe { x86_64::find_sse2(&haystack, ENGLISH, false) }, find_scalar(&haystack, ENGLISH, false, 0));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_find() {
+        assert_eq!(find_letter(b"", ENGLISH), None);
+        assert_eq!(find_letter(b"  ,;123 \xC3\xA9\xF0\x9F\x90\xB7!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!!x", ENGLISH), Some(50));
+        assert_eq!(find_non_letter(b"HelloWorldHelloWorldHelloWorldHelloWorld'", ENGLISH), Some(40));
+        assert_eq!(find_non_letter(b"HelloWorldHelloWorldHelloWorldHelloWorld", ENGLISH), None);
+    }
+
+    #[test]
+    fn test_find_non_letter_with_joiners() {
+        let classes = &ByteClasses::ENGLISH.with_joiners(b"-.");
+        assert_eq!(find_non_letter(b"", classes), None);
+        assert_eq!(find_non_letter(b"well-known-ish words", classes), Some(14));
+        assert_eq!(find_non_letter(b"-known", classes), None);
+        assert_eq!(find_non_letter(b"ell--known", classes), Some(3));
+        assert_eq!(find_non_letter(b"ell-.known", classes), Some(3));
+        assert_eq!(find_non_letter(b"e.g.", classes), Some(3));
+        assert_eq!(find_non_letter(b"ell-", classes), Some(3));
+        assert_eq!(find_non_letter(b"ell-1", classes), Some(3));
+        assert_eq!(find_non_letter(b"isn't", classes), Some(3));
+        assert_eq!(find_non_letter(b"well-known", ENGLISH), Some(4));
+
+        //Long enough that the vectorized scanner would be used without joiners
+        assert_eq!(find_non_letter(b"HelloWorld-HelloWorld-HelloWorld-HelloWorld!", classes), Some(43));
+        assert_eq!(find_letter(b"--------------------------------------------a", classes), Some(44));
+    }
+}
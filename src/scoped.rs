@@ -0,0 +1,173 @@
+/* scoped.rs
+ * By: John Jekel
+ * Copyright (C) 2022 John Jekel
+ * See the LICENSE file at the root of the project for licensing info.
+ *
+ * Translation of only the parts of a text selected by a regex
+ *
+*/
+
+/* Imports */
+
+use std::ops::Range;
+
+use regex::Regex;
+
+use crate::byte_string::translate_with as translate_byte_string_with;
+use crate::spans::{Spans, SpanKind};
+use crate::style::Style;
+use crate::utf8::translated_string;
+
+/* Types */
+
+///Which parts of a text [`translate_scoped()`] translates.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum RegexScope {
+    ///Only the text matching the regex
+    Matches,
+    ///Only the text in the capture groups of each match (use non-capturing groups `(?:...)` for parts that shouldn't be translated)
+    CaptureGroups,
+    ///Everything except the text matching the regex
+    Unmatched,
+}
+
+/* Functions */
+
+///Translates only the parts of a multi-word string (including punctuation) selected by a regex, copying everything else as-is!
+///
+///Words are found in the text as a whole (like [`crate::translate()`]), and a word is translated only if all of it is within a selected part.
+///So a word is never split in two by where a match starts or ends, and contraction suffixes (ex. the 't in "isn't") are handled the same as usual.
+///
+///Requires the `regex` feature.
+///
+///# Examples
+///
+///```
+///use anslatortray::{translate_scoped, RegexScope};
+///use regex::Regex;
+///
+/////Only translate the dialogue in a script
+///let script = "ROMEO: \"She speaks!\" (aside) JULIET: \"Ay me!\"";
+///let dialogue = Regex::new("\"([^\"]*)\"").unwrap();
+///assert_eq!(translate_scoped(script, &dialogue, RegexScope::CaptureGroups), "ROMEO: \"Eshay eaksspay!\" (aside) JULIET: \"Ayway emay!\"");
+///
+/////Leave placeholders alone
+///let template = "Hello {{name}}, welcome to {{place}}!";
+///let placeholders = Regex::new(r"\{\{[^}]*\}\}").unwrap();
+///assert_eq!(translate_scoped(template, &placeholders, RegexScope::Unmatched), "Ellohay {{name}}, elcomeway otay {{place}}!");
+///```
+pub fn translate_scoped(english: &str, regex: &Regex, scope: RegexScope) -> String {
+    return translate_scoped_with(english, regex, scope, &Style::WAY);
+}
+
+///Translates only the parts of a multi-word string (including punctuation) selected by a regex using a [`Style`]; see [`translate_scoped()`].
+///
+///Requires the `regex` feature.
+///
+///# Examples
+///
+///```
+///use anslatortray::{translate_scoped_with, RegexScope, Style};
+///use regex::Regex;
+///
+///let code = "let greeting = \"Hello world\"; // Says hello";
+///let strings = Regex::new("\"[^\"]*\"").unwrap();
+///assert_eq!(translate_scoped_with(code, &strings, RegexScope::Matches, &Style::FERB), "let greeting = \"Elloherb orldwerb\"; // Says hello");
+///```
+pub fn translate_scoped_with(english: &str, regex: &Regex, scope: RegexScope, style: &Style) -> String {
+    let selected = selected_ranges(english, regex, scope);
+
+    let mut pig_latin_string_bytes = Vec::<u8>::with_capacity(english.len() * 2);//Plenty of headroom in case the words are very small or the suffixes are long
+    let mut selected_iter = selected.iter().peekable();
+    for span in Spans::new(english.as_bytes(), style.classes()) {
+        let text = &english.as_bytes()[span.range.clone()];
+        if span.kind != SpanKind::Word {
+            pig_latin_string_bytes.extend_from_slice(text);
+            continue;
+        }
+
+        //Both the words and the selected ranges are in order, so skip past ranges that end before this word
+        while selected_iter.next_if(|range| range.end <= span.range.start).is_some() {}
+
+        if selected_iter.peek().is_some_and(|range| (range.start <= span.range.start) && (span.range.end <= range.end)) {
+            translate_byte_string_with(text, style, &mut pig_latin_string_bytes);
+        } else {
+            pig_latin_string_bytes.extend_from_slice(text);
+        }
+    }
+
+    return translated_string(pig_latin_string_bytes);
+}
+
+//Returns the parts of the text to translate, in order and not overlapping
+fn selected_ranges(english: &str, regex: &Regex, scope: RegexScope) -> Vec<Range<usize>> {
+    match scope {
+        RegexScope::Matches => {
+            return regex.find_iter(english).map(|found| found.range()).collect();
+        },
+        RegexScope::CaptureGroups => {
+            //Groups can be nested, so merge any that overlap
+            let mut ranges = Vec::<Range<usize>>::new();
+            for captures in regex.captures_iter(english) {
+                let mut groups: Vec<Range<usize>> = captures.iter().skip(1).flatten().map(|group| group.range()).collect();
+                groups.sort_unstable_by_key(|group| group.start);
+
+                for group in groups {
+                    match ranges.last_mut() {
+                        Some(last) if group.start <= last.end => last.end = last.end.max(group.end),
+                        _ => ranges.push(group),
+                    }
+                }
+            }
+            return ranges;
+        },
+        RegexScope::Unmatched => {
+            let mut ranges = Vec::<Range<usize>>::new();
+            let mut start: usize = 0;
+            for found in regex.find_iter(english) {
+                ranges.push(start..found.start());
+                start = found.end();
+            }
+            ranges.push(start..english.len());
+            return ranges;
+        },
+    }
+}
+
+/* Tests */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ENGLISH: &str = "Let's try some edge cases: ISn't THIS COOL? naïve mcDONALD's, bcd 123 Hyphens-are-difficult-aren't-they?' A";
+
+    #[test]
+    fn test_translate_scoped_everything_or_nothing() {
+        let everything = Regex::new("(?s)(.*)").unwrap();
+        let nothing = Regex::new("$^").unwrap();
+
+        for scope in [RegexScope::Matches, RegexScope::CaptureGroups] {
+            assert_eq!(translate_scoped(ENGLISH, &everything, scope), crate::translate(ENGLISH));
+            assert_eq!(translate_scoped(ENGLISH, &nothing, scope), ENGLISH);
+        }
+        assert_eq!(translate_scoped(ENGLISH, &everything, RegexScope::Unmatched), ENGLISH);
+        assert_eq!(translate_scoped(ENGLISH, &nothing, RegexScope::Unmatched), crate::translate(ENGLISH));
+        assert_eq!(translate_scoped("", &everything, RegexScope::Matches), "");
+    }
+
+    #[test]
+    fn test_translate_scoped_word_boundaries() {
+        //Words that are only partly selected are left alone, and contractions stay together
+        let regex = Regex::new("ll|isn't|(x) (y)").unwrap();
+        assert_eq!(translate_scoped("all isn't x y z", &regex, RegexScope::Matches), "all isnway't xway yway z");
+        assert_eq!(translate_scoped("all isn't x y z", &regex, RegexScope::Unmatched), "all isn't x y zway");
+        assert_eq!(translate_scoped("all isn't x y z", &regex, RegexScope::CaptureGroups), "all isn't xway yway z");
+
+        //Nested and optional groups
+        let regex = Regex::new("<((a) b)?(c)?>").unwrap();
+        assert_eq!(selected_ranges("<a b><c><>", &regex, RegexScope::CaptureGroups), [1..4, 6..7]);
+        assert_eq!(translate_scoped("<a b><c><>", &regex, RegexScope::CaptureGroups), "<away bway><cway><>");
+    }
+}
@@ -0,0 +1,136 @@
+/* selection.rs
+ * By: John Jekel
+ * Copyright (C) 2022 John Jekel
+ * See the LICENSE file at the root of the project for licensing info.
+ *
+ * Translation of only some of the words in a text (ex. for word puzzles)
+ *
+*/
+
+/* Imports */
+
+use alloc::string::String;
+
+use crate::hook::{translate_with_hook, WordInfo, Decision};
+use crate::style::Style;
+
+/* Types */
+
+///Which words [`translate_selected()`] translates; the rest are copied as-is.
+///
+///The selection only depends on the words themselves, their positions and the seed, so the same text and selection always give the same result.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Selection {
+    ///Every word
+    All,
+    ///Every Nth word, starting with the Nth (so 1 is every word, and 0 is none)
+    EveryNth(usize),
+    ///A pseudo-random percentage of the words (0 to 100), chosen the same way every time for a given seed
+    Random {
+        ///Roughly what percentage of the words to translate (anything above 100 is treated as 100)
+        percent: u8,
+        ///The seed that decides which words are chosen
+        seed: u64,
+    },
+    ///Words with more than this many letters (not counting contraction suffixes like the 't in "isn't")
+    LongerThan(usize),
+}
+
+/* Associated Functions and Methods */
+
+impl Selection {
+    ///Returns whether a word is selected for translation (useful for combining a selection with other rules in [`translate_with_hook()`]).
+    pub fn selects(&self, word: &WordInfo<'_>) -> bool {
+        return match *self {
+            Selection::All => true,
+            Selection::EveryNth(n) => (n != 0) && (word.index + 1).is_multiple_of(n),
+            Selection::Random { percent, seed } => (mix(seed, word.index) % 100) < (percent as u64),
+            Selection::LongerThan(len) => word.word.len() > len,
+        };
+    }
+}
+
+/* Functions */
+
+///Translates only the words of a multi-word string (including punctuation) picked by a [`Selection`], copying the rest as-is!
+///
+///# Examples
+///
+///```
+///use anslatortray::{translate_selected, Selection};
+///
+///let english = "The quick brown fox jumps over the lazy dog";
+///assert_eq!(translate_selected(english, Selection::EveryNth(3)), "The quick ownbray fox jumps overway the lazy ogday");
+///assert_eq!(translate_selected(english, Selection::LongerThan(4)), "The uickqay ownbray fox umpsjay over the lazy dog");
+///
+/////The same seed always picks the same words, so puzzles can be reproduced
+///let puzzle = translate_selected(english, Selection::Random { percent: 50, seed: 42 });
+///assert_eq!(puzzle, "Ethay quick brown fox jumps over ethay azylay ogday");
+///```
+pub fn translate_selected(english: &str, selection: Selection) -> String {
+    return translate_selected_with(english, &Style::WAY, selection);
+}
+
+///Translates only the words of a multi-word string (including punctuation) picked by a [`Selection`] using a [`Style`]; see [`translate_selected()`].
+///
+///# Examples
+///
+///```
+///use anslatortray::{translate_selected_with, Selection, Style};
+///
+///assert_eq!(translate_selected_with("Where's Perry? Isn't he here?", &Style::FERB, Selection::EveryNth(2)), "Where's Erryperb? Isn't eherb here?");
+///```
+pub fn translate_selected_with(english: &str, style: &Style, selection: Selection) -> String {
+    return translate_with_hook(english, style, |word| if selection.selects(word) { Decision::Translate } else { Decision::Keep });
+}
+
+//Hashes the seed and the index of a word (SplitMix64), so each word is chosen independently of the others and the same way on every platform
+fn mix(seed: u64, index: usize) -> u64 {
+    let mut z = seed.wrapping_add((index as u64).wrapping_add(1).wrapping_mul(0x9E3779B97F4A7C15));
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+    return z ^ (z >> 31);
+}
+
+/* Tests */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::style::RulesVersion;
+
+    const ENGLISH: &str = "Let's try some edge cases: ISn't THIS COOL? naïve mcDONALD's, bcd 123 Hyphens-are-difficult-aren't-they?' A";
+
+    #[test]
+    fn test_translate_selected_all_or_nothing() {
+        for style in [Style::WAY, Style::FERB, Style::HAY.with_rules(RulesVersion::V0_4)] {
+            let expected = crate::translate_with(ENGLISH, &style);
+            for selection in [Selection::All, Selection::EveryNth(1), Selection::Random { percent: 100, seed: 7 }, Selection::Random { percent: 255, seed: 7 }, Selection::LongerThan(0)] {
+                assert_eq!(translate_selected_with(ENGLISH, &style, selection), expected, "{:?}", selection);
+            }
+            for selection in [Selection::EveryNth(0), Selection::Random { percent: 0, seed: 7 }, Selection::LongerThan(usize::MAX)] {
+                assert_eq!(translate_selected_with(ENGLISH, &style, selection), ENGLISH, "{:?}", selection);
+            }
+        }
+    }
+
+    #[test]
+    fn test_translate_selected_random() {
+        let english = "a ".repeat(10_000);
+        let translated_count = |percent: u8, seed: u64| translate_selected(&english, Selection::Random { percent, seed }).matches("away").count();
+
+        //Roughly the right fraction of the words, and a different set of them for each seed
+        for percent in [10, 30, 50, 90] {
+            let count = translated_count(percent, 1234);
+            assert!(count.abs_diff(percent as usize * 100) < 300, "{}% gave {}", percent, count);
+        }
+        let seed_1 = translate_selected(&english, Selection::Random { percent: 50, seed: 1 });
+        let seed_2 = translate_selected(&english, Selection::Random { percent: 50, seed: 2 });
+        assert_ne!(seed_1, seed_2);
+        assert_eq!(seed_1, translate_selected(&english, Selection::Random { percent: 50, seed: 1 }));
+
+        //The hash is pinned, so seeded puzzles stay the same on every platform and release
+        assert_eq!(mix(0, 0), 0xE220A8397B1DCDAF);
+    }
+}
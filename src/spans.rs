@@ -0,0 +1,129 @@
+/* spans.rs
+ * By: John Jekel
+ * Copyright (C) 2022 John Jekel
+ * See the LICENSE file at the root of the project for licensing info.
+ *
+ * Splits text into words, contraction suffixes, and the characters in-between them (the same way the translation loop does)
+ *
+*/
+
+/* Imports */
+
+use core::ops::Range;
+
+use crate::classes::ByteClasses;
+use crate::scan::{find_letter, find_non_letter};
+
+/* Types */
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum SpanKind {
+    //Anything that isn't an ASCII letter (whitespace, symbols, numbers, UTF-8, etc.); copied as-is
+    Separator,
+    //A run of ASCII letters (and joiners between them); translated
+    Word,
+    //An apostrophe directly after a word followed by at least one letter (ex. the 't in "isn't"); copied as-is
+    ContractionSuffix,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct Span {
+    pub(crate) kind: SpanKind,
+    pub(crate) range: Range<usize>,
+}
+
+//Iterator over the spans of a byte string. Concatenating every span gives back the original string.
+#[derive(Clone)]
+pub(crate) struct Spans<'a> {
+    english: &'a [u8],
+    classes: &'a ByteClasses,
+    index: usize,
+    after_word: bool,
+}
+
+/* Associated Functions and Methods */
+
+impl<'a> Spans<'a> {
+    pub(crate) fn new(english: &'a [u8], classes: &'a ByteClasses) -> Spans<'a> {
+        return Spans { english, classes, index: 0, after_word: false };
+    }
+}
+
+impl Iterator for Spans<'_> {
+    type Item = Span;
+
+    fn next(&mut self) -> Option<Span> {
+        if self.index == self.english.len() {
+            return None;
+        }
+
+        let start = self.index;
+        let after_word = self.after_word;
+        self.after_word = false;
+
+        let kind: SpanKind;
+        if self.classes.is_letter(self.english[start]) {
+            kind = SpanKind::Word;
+            self.index = end_of_letters(self.english, self.classes, start);
+            self.after_word = true;
+        } else if after_word && self.classes.is_apostrophe(self.english[start]) && (start + 1 < self.english.len()) && self.classes.is_letter(self.english[start + 1]) {
+            kind = SpanKind::ContractionSuffix;
+            self.index = end_of_letters(self.english, self.classes, start + 1);
+        } else {
+            kind = SpanKind::Separator;
+            //We know the first character is not the start of a word or contraction suffix
+            self.index = match find_letter(&self.english[(start + 1)..], self.classes) {
+                Some(length) => start + 1 + length,
+                None => self.english.len(),
+            };
+        }
+
+        return Some(Span { kind, range: start..self.index });
+    }
+}
+
+/* Functions */
+
+//Returns the index one past the end of the run of letters (and joiners between them) starting at start
+fn end_of_letters(english: &[u8], classes: &ByteClasses, start: usize) -> usize {
+    return match find_non_letter(&english[start..], classes) {
+        Some(length) => start + length,
+        None => english.len(),
+    };
+}
+
+/* Tests */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spans_of(english: &str) -> Vec<(SpanKind, &str)> {
+        return spans_with(english, &ByteClasses::ENGLISH);
+    }
+
+    fn spans_with<'a>(english: &'a str, classes: &ByteClasses) -> Vec<(SpanKind, &'a str)> {
+        return Spans::new(english.as_bytes(), classes).map(|span| (span.kind, &english[span.range])).collect();
+    }
+
+    #[test]
+    fn test_spans() {
+        use SpanKind::*;
+
+        assert_eq!(spans_of(""), vec![]);
+        assert_eq!(spans_of("Hello world!"), vec![(Word, "Hello"), (Separator, " "), (Word, "world"), (Separator, "!")]);
+        assert_eq!(spans_of("  isn't it's'"), vec![(Separator, "  "), (Word, "isn"), (ContractionSuffix, "'t"), (Separator, " "), (Word, "it"), (ContractionSuffix, "'s"), (Separator, "'")]);
+        assert_eq!(spans_of("dogs' 'tis"), vec![(Word, "dogs"), (Separator, "' '"), (Word, "tis")]);
+        assert_eq!(spans_of("café123"), vec![(Word, "caf"), (Separator, "é123")]);
+
+        let backticks = ByteClasses::new(b"aeiou", b"`");
+        assert_eq!(spans_with("isn`t isn't", &backticks), vec![(Word, "isn"), (ContractionSuffix, "`t"), (Separator, " "), (Word, "isn"), (Separator, "'"), (Word, "t")]);
+
+        let hyphenated = ByteClasses::ENGLISH.with_joiners(b"-");
+        assert_eq!(spans_with("well-known -ish- a--b isn't-it '-s", &hyphenated), vec![
+            (Word, "well-known"), (Separator, " -"), (Word, "ish"), (Separator, "- "), (Word, "a"), (Separator, "--"), (Word, "b"), (Separator, " "),
+            (Word, "isn"), (ContractionSuffix, "'t-it"), (Separator, " '-"), (Word, "s"),
+        ]);
+        assert_eq!(spans_of("well-known"), vec![(Word, "well"), (Separator, "-"), (Word, "known")]);
+    }
+}
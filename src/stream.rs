@@ -0,0 +1,437 @@
+/* stream.rs
+ * By: John Jekel
+ * Copyright (C) 2022 John Jekel
+ * See the LICENSE file at the root of the project for licensing info.
+ *
+ * Translation of text as it flows through std::io::Write and std::io::Read
+ *
+*/
+
+/* Imports */
+
+use std::io::{self, Read, Write};
+
+use crate::byte_string::translate_with as translate_byte_string_with;
+use crate::scan::{find_letter, find_non_letter};
+use crate::classes::ByteClasses;
+use crate::style::Style;
+
+/* Constants */
+
+//How much is read from the inner reader at once
+const READ_CHUNK_SIZE: usize = 8 * 1024;
+
+/* Types */
+
+//Translates text that arrives in chunks, keeping only the word that straddles a chunk boundary
+//Multi-byte UTF-8 sequences never contain ASCII letters, so they are copied as-is even if they are split between chunks
+#[derive(Clone, Debug)]
+pub(crate) struct ChunkTranslator {
+    style: Style,
+    state: State,
+    partial_word: Vec<u8>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum State {
+    //In-between words (or at the very start)
+    Separator,
+    //Inside a word (which is in partial_word)
+    Word,
+    //Directly after a word
+    AfterWord,
+    //Directly after the apostrophe following a word
+    AfterApostrophe,
+    //Inside the letters of a contraction suffix
+    ContractionSuffix,
+    //A joiner ended the chunk directly after the letters of a word; it is at the end of partial_word until we know if a letter follows it
+    WordJoiner,
+    //A joiner ended the chunk directly after the letters of a contraction suffix; it is in partial_word until we know if a letter follows it
+    ContractionSuffixJoiner,
+}
+
+///Translates everything written to it, passing the translation on to another [`Write`]r.
+///
+///Only the word that straddles the end of each write is buffered, so arbitrarily large inputs can be translated in bounded memory.
+///Call [`PigLatinWriter::finish()`] once done to translate that last word; if you don't, it is written (ignoring errors) when the writer is dropped.
+///
+///# Examples
+///
+///```
+///use std::io::Write;
+///use anslatortray::{PigLatinWriter, Style};
+///
+///let mut writer = PigLatinWriter::new(Vec::<u8>::new(), Style::WAY);
+///writer.write_all(b"Hello wo").unwrap();
+///writer.write_all(b"rld, isn").unwrap();
+///writer.write_all(b"'t it?").unwrap();
+///
+///let pig_latin = writer.finish().unwrap();
+///assert_eq!(pig_latin, b"Ellohay orldway, isnway't itway?");
+///```
+#[derive(Debug)]
+pub struct PigLatinWriter<W: Write> {
+    inner: Option<W>,//Only None once finish() has taken it
+    translator: ChunkTranslator,
+    buffer: Vec<u8>,
+}
+
+///Translates everything read from another [`Read`]er.
+///
+///The inner reader is read in small chunks, and only the word that straddles the end of each chunk is buffered,
+///so arbitrarily large inputs can be translated in bounded memory.
+///
+///# Examples
+///
+///```
+///use std::io::Read;
+///use anslatortray::{PigLatinReader, Style};
+///
+///let mut reader = PigLatinReader::new("Where's Perry?".as_bytes(), Style::FERB);
+///
+///let mut pig_latin = String::new();
+///reader.read_to_string(&mut pig_latin).unwrap();
+///assert_eq!(pig_latin, "Erewherb's Erryperb?");
+///```
+#[derive(Debug)]
+pub struct PigLatinReader<R: Read> {
+    inner: R,
+    translator: ChunkTranslator,
+    input: Vec<u8>,
+    output: Vec<u8>,
+    output_index: usize,
+    finished: bool,
+}
+
+/* Associated Functions and Methods */
+
+impl ChunkTranslator {
+    pub(crate) fn new(style: Style) -> ChunkTranslator {
+        return ChunkTranslator { style, state: State::Separator, partial_word: Vec::new() };
+    }
+
+    //Translates as much of the chunk as possible, appending to pig_latin_string
+    pub(crate) fn translate_chunk(&mut self, chunk: &[u8], pig_latin_string: &mut Vec<u8>) {
+        let mut index: usize = 0;
+        while index < chunk.len() {
+            match self.state {
+                State::Separator => {
+                    let end = end_of(chunk, index, self.style.classes(), find_letter);
+                    pig_latin_string.extend_from_slice(&chunk[index..end]);
+                    index = end;
+                    if index < chunk.len() {
+                        self.state = State::Word;
+                    }
+                },
+                State::Word => {
+                    let end = end_of(chunk, index, self.style.classes(), find_non_letter);
+                    self.partial_word.extend_from_slice(&chunk[index..end]);
+                    index = end;
+                    if ends_with_joiner(chunk, index, self.style.classes()) {
+                        self.partial_word.push(chunk[index]);
+                        index += 1;
+                        self.state = State::WordJoiner;
+                    } else if index < chunk.len() {//The word ended in this chunk
+                        translate_byte_string_with(&self.partial_word, &self.style, pig_latin_string);
+                        self.partial_word.truncate(0);
+                        self.state = State::AfterWord;
+                    }
+                },
+                State::AfterWord => {
+                    if self.style.classes().is_apostrophe(chunk[index]) {
+                        pig_latin_string.push(chunk[index]);
+                        index += 1;
+                        self.state = State::AfterApostrophe;
+                    } else {
+                        self.state = State::Separator;
+                    }
+                },
+                State::AfterApostrophe => {
+                    self.state = if self.style.classes().is_letter(chunk[index]) { State::ContractionSuffix } else { State::Separator };
+                },
+                State::ContractionSuffix => {
+                    //We never translate the contraction suffix of a word, so just copy its letters as-is
+                    let end = end_of(chunk, index, self.style.classes(), find_non_letter);
+                    pig_latin_string.extend_from_slice(&chunk[index..end]);
+                    index = end;
+                    if ends_with_joiner(chunk, index, self.style.classes()) {
+                        self.partial_word.push(chunk[index]);
+                        index += 1;
+                        self.state = State::ContractionSuffixJoiner;
+                    } else if index < chunk.len() {
+                        self.state = State::Separator;
+                    }
+                },
+                State::WordJoiner => {
+                    if self.style.classes().is_letter(chunk[index]) {
+                        self.state = State::Word;
+                    } else {
+                        self.finish_word_joiner(pig_latin_string);
+                        self.state = State::Separator;
+                    }
+                },
+                State::ContractionSuffixJoiner => {
+                    //Either way the joiner is copied as-is; it just decides if the letters after it are still part of the suffix
+                    pig_latin_string.extend_from_slice(&self.partial_word);
+                    self.partial_word.truncate(0);
+                    self.state = if self.style.classes().is_letter(chunk[index]) { State::ContractionSuffix } else { State::Separator };
+                },
+            }
+        }
+    }
+
+    //Translates the word at the very end of the text (if any), getting ready for new text
+    pub(crate) fn finish(&mut self, pig_latin_string: &mut Vec<u8>) {
+        match self.state {
+            State::Word => translate_byte_string_with(&self.partial_word, &self.style, pig_latin_string),
+            State::WordJoiner => self.finish_word_joiner(pig_latin_string),
+            State::ContractionSuffixJoiner => pig_latin_string.extend_from_slice(&self.partial_word),
+            _ => {},
+        }
+        self.partial_word.truncate(0);
+        self.state = State::Separator;
+    }
+
+    //The joiner at the end of partial_word turned out not to be followed by a letter, so it ends the word instead
+    fn finish_word_joiner(&mut self, pig_latin_string: &mut Vec<u8>) {
+        let joiner = self.partial_word.pop().unwrap();
+        translate_byte_string_with(&self.partial_word, &self.style, pig_latin_string);
+        pig_latin_string.push(joiner);
+        self.partial_word.truncate(0);
+    }
+}
+
+impl<W: Write> PigLatinWriter<W> {
+    ///Creates a new writer that translates with the given [`Style`] and writes the translation to `inner`.
+    pub fn new(inner: W, style: Style) -> PigLatinWriter<W> {
+        return PigLatinWriter { inner: Some(inner), translator: ChunkTranslator::new(style), buffer: Vec::new() };
+    }
+
+    ///Translates the last word (if the text ended in the middle of one), flushes, and returns the inner writer.
+    pub fn finish(mut self) -> io::Result<W> {
+        self.finish_translation()?;
+        return Ok(self.inner.take().unwrap());
+    }
+
+    ///Returns a reference to the inner writer.
+    pub fn get_ref(&self) -> &W {
+        return self.inner.as_ref().unwrap();
+    }
+
+    fn finish_translation(&mut self) -> io::Result<()> {
+        self.translator.finish(&mut self.buffer);
+        self.write_buffer()?;
+        return self.inner.as_mut().unwrap().flush();
+    }
+
+    //Writes out translated text that hasn't made it to the inner writer yet, keeping whatever couldn't be written if it fails
+    fn write_buffer(&mut self) -> io::Result<()> {
+        let inner = self.inner.as_mut().unwrap();
+        let mut written: usize = 0;
+        let result = loop {
+            if written == self.buffer.len() {
+                break Ok(());
+            }
+            match inner.write(&self.buffer[written..]) {
+                Ok(0) => { break Err(io::Error::new(io::ErrorKind::WriteZero, "failed to write the translated text")); },
+                Ok(bytes_written) => { written += bytes_written; },
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => {},
+                Err(error) => { break Err(error); },
+            }
+        };
+        self.buffer.drain(..written);
+        return result;
+    }
+}
+
+impl<W: Write> Write for PigLatinWriter<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        //Write out what previous writes left behind first: if that fails, buf hasn't been used yet, so it is safe to retry with it
+        self.write_buffer()?;
+
+        //Once buf is translated it has been used up, so it counts as written even if the inner writer fails;
+        //whatever couldn't be written is kept for the next write() or flush(), which report the error if it persists
+        self.translator.translate_chunk(buf, &mut self.buffer);
+        let _ = self.write_buffer();
+        return Ok(buf.len());
+    }
+
+    ///Writes out any translated text held back by an earlier error, and flushes the inner writer.
+    ///
+    ///Note that if the text written so far ends in the middle of a word, that word is held back until more text is written or [`PigLatinWriter::finish()`] is called.
+    fn flush(&mut self) -> io::Result<()> {
+        self.write_buffer()?;
+        return self.inner.as_mut().unwrap().flush();
+    }
+}
+
+impl<W: Write> Drop for PigLatinWriter<W> {
+    fn drop(&mut self) {
+        if self.inner.is_some() {
+            let _ = self.finish_translation();//Like BufWriter, we can't report errors when dropped
+        }
+    }
+}
+
+impl<R: Read> PigLatinReader<R> {
+    ///Creates a new reader that translates text read from `inner` with the given [`Style`].
+    pub fn new(inner: R, style: Style) -> PigLatinReader<R> {
+        return PigLatinReader {
+            inner,
+            translator: ChunkTranslator::new(style),
+            input: vec![0; READ_CHUNK_SIZE],
+            output: Vec::with_capacity(READ_CHUNK_SIZE * 2),
+            output_index: 0,
+            finished: false,
+        };
+    }
+
+    ///Returns the inner reader.
+    pub fn into_inner(self) -> R {
+        return self.inner;
+    }
+}
+
+impl<R: Read> Read for PigLatinReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        //Translate more text until we have something to give back (a chunk could be a single partial word)
+        while self.output_index == self.output.len() {
+            if self.finished {
+                return Ok(0);
+            }
+
+            self.output.truncate(0);
+            self.output_index = 0;
+
+            let bytes_read = match self.inner.read(&mut self.input) {
+                Ok(bytes_read) => bytes_read,
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => { continue; },
+                Err(error) => { return Err(error); },
+            };
+            if bytes_read == 0 {
+                self.translator.finish(&mut self.output);
+                self.finished = true;
+            } else {
+                self.translator.translate_chunk(&self.input[..bytes_read], &mut self.output);
+            }
+        }
+
+        let available = &self.output[self.output_index..];
+        let amount = available.len().min(buf.len());
+        buf[..amount].copy_from_slice(&available[..amount]);
+        self.output_index += amount;
+        return Ok(amount);
+    }
+}
+
+/* Functions */
+
+//Returns the index of the first byte at or after start found by find (or the length of the chunk if there isn't one)
+//Whether the letters before index are followed by a joiner that is the very last byte of the chunk (so we can't tell yet if it is between letters)
+fn ends_with_joiner(chunk: &[u8], index: usize, classes: &ByteClasses) -> bool {
+    return (index + 1 == chunk.len()) && classes.is_joiner(chunk[index]);
+}
+
+fn end_of(chunk: &[u8], start: usize, classes: &ByteClasses, find: fn(&[u8], &ByteClasses) -> Option<usize>) -> usize {
+    return match find(&chunk[start..], classes) {
+        Some(length) => start + length,
+        None => chunk.len(),
+    };
+}
+
+/* Tests */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::style::RulesVersion;
+
+    const ENGLISH: &str = "Let's try some edge cases: ISn't THIS COOL? naïve mcDONALD's, bcd 123 Hyphens-are-difficult-aren't-they?' Ok";
+
+    #[test]
+    fn test_chunk_translator_every_split() {
+        static HYPHENATED: ByteClasses = ByteClasses::ENGLISH.with_joiners(b"-");
+        for style in [Style::WAY, Style::FERB.with_rules(RulesVersion::V0_4), Style::WAY.with_classes(&HYPHENATED)] {
+            let expected = crate::translate_with(ENGLISH, &style);
+
+            //Every possible chunk size, including splitting UTF-8 sequences, words, and contractions
+            for chunk_size in 1..=ENGLISH.len() {
+                let mut translator = ChunkTranslator::new(style);
+                let mut pig_latin = Vec::new();
+                for chunk in ENGLISH.as_bytes().chunks(chunk_size) {
+                    translator.translate_chunk(chunk, &mut pig_latin);
+                }
+                translator.finish(&mut pig_latin);
+                assert_eq!(String::from_utf8(pig_latin).unwrap(), expected, "chunk size {}", chunk_size);
+            }
+        }
+    }
+
+    #[test]
+    fn test_pig_latin_writer_drop() {
+        let mut pig_latin = Vec::new();
+        {
+            let mut writer = PigLatinWriter::new(&mut pig_latin, Style::WAY);
+            writer.write_all(b"Hel").unwrap();
+            writer.write_all(b"lo world").unwrap();
+        }
+        assert_eq!(pig_latin, b"Ellohay orldway");
+    }
+
+    //Fails the given number of times, then accepts at most 4 bytes per write
+    struct FlakyWriter {
+        failures: usize,
+        written: Vec<u8>,
+    }
+
+    impl Write for FlakyWriter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            if self.failures > 0 {
+                self.failures -= 1;
+                return Err(io::Error::from(io::ErrorKind::WouldBlock));
+            }
+            let amount = buf.len().min(4);
+            self.written.extend_from_slice(&buf[..amount]);
+            return Ok(amount);
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            return Ok(());
+        }
+    }
+
+    #[test]
+    fn test_pig_latin_writer_inner_errors() {
+        //The inner writer fails while writing the translation of buf: buf still counts as written, and isn't translated twice
+        let mut writer = PigLatinWriter::new(FlakyWriter { failures: 1, written: Vec::new() }, Style::WAY);
+        assert_eq!(writer.write(b"Hello world ").unwrap(), 12);
+        assert_eq!(writer.get_ref().written, b"");
+
+        //The inner writer fails while writing out what was held back: nothing of buf is used, so retrying with it is fine
+        writer.inner.as_mut().unwrap().failures = 1;
+        assert_eq!(writer.write(b"again").unwrap_err().kind(), io::ErrorKind::WouldBlock);
+        assert_eq!(writer.write(b"again").unwrap(), 5);
+        assert_eq!(writer.get_ref().written, b"Ellohay orldway ");
+
+        writer.inner.as_mut().unwrap().failures = 2;
+        writer.write_all(b" and").unwrap();
+        assert_eq!(writer.flush().unwrap_err().kind(), io::ErrorKind::WouldBlock);
+        writer.flush().unwrap();
+        assert_eq!(writer.finish().unwrap().written, b"Ellohay orldway againway andway");
+    }
+
+    #[test]
+    fn test_pig_latin_reader_small_reads() {
+        let mut reader = PigLatinReader::new(ENGLISH.as_bytes(), Style::WAY);
+        let mut pig_latin = Vec::new();
+        let mut buf = [0u8; 3];
+        loop {
+            let bytes_read = reader.read(&mut buf).unwrap();
+            if bytes_read == 0 {
+                break;
+            }
+            pig_latin.extend_from_slice(&buf[..bytes_read]);
+        }
+        assert_eq!(String::from_utf8(pig_latin).unwrap(), crate::translate(ENGLISH));
+    }
+}
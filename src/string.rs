@@ -9,7 +9,16 @@
 
 /* Imports */
 
+use alloc::borrow::Cow;
+use alloc::string::String;
+use alloc::vec::Vec;
+
 use crate::byte_string::translate_with_style_lower_and_upper_suffixes as translate_byte_string_with_style_lower_and_upper_suffixes;
+use crate::byte_string::translate_with as translate_byte_string_with;
+use crate::byte_string::translate_with_diagnostics as translate_byte_string_with_diagnostics;
+use crate::style::{Style, WordRules};
+use crate::diagnostics::Diagnostic;
+use crate::utf8::translated_string;
 
 /* Functions */
 
@@ -80,7 +89,7 @@ pub fn translate(english: &str) -> String {
 ///assert_eq!(translate_way("Hyphens-are-difficult-aren't-they?"), "Yphenshay-areway-ifficultday-arenway't-eythay?");
 ///```
 pub fn translate_way(english: &str) -> String {
-    return translate_with_style_lower_and_upper_suffixes(english, "ay", "way", "AY", "WAY");
+    return translate_with_style_lower_and_upper_suffixes(english, WordRules::LATEST, "ay", "way", "AY", "WAY");
 }
 
 ///Translates a multi-word string (including punctuation) into Pig Latin (yay-style)!
@@ -114,7 +123,7 @@ pub fn translate_way(english: &str) -> String {
 ///assert_eq!(translate_yay("Hyphens-are-difficult-aren't-they?"), "Yphenshay-areyay-ifficultday-arenyay't-eythay?");
 ///```
 pub fn translate_yay(english: &str) -> String {
-    return translate_with_style_lower_and_upper_suffixes(english, "ay", "yay", "AY", "YAY");
+    return translate_with_style_lower_and_upper_suffixes(english, WordRules::LATEST, "ay", "yay", "AY", "YAY");
 }
 
 ///Translates a multi-word string (including punctuation) into Pig Latin (hay-style)!
@@ -148,7 +157,7 @@ pub fn translate_yay(english: &str) -> String {
 ///assert_eq!(translate_hay("Hyphens-are-difficult-aren't-they?"), "Yphenshay-arehay-ifficultday-arenhay't-eythay?");
 ///```
 pub fn translate_hay(english: &str) -> String {
-    return translate_with_style_lower_and_upper_suffixes(english, "ay", "hay", "AY", "HAY");
+    return translate_with_style_lower_and_upper_suffixes(english, WordRules::LATEST, "ay", "hay", "AY", "HAY");
 }
 
 ///Translates a multi-word string (including punctuation) into Ferb Latin!
@@ -180,7 +189,7 @@ pub fn translate_hay(english: &str) -> String {
 ///assert_eq!(translate_ferb("Hyphens-are-difficult-aren't-they?"), "Yphensherb-areferb-ifficultderb-arenferb't-eytherb?");
 ///```
 pub fn translate_ferb(english: &str) -> String {
-    return translate_with_style_lower_and_upper_suffixes(english, "erb", "ferb", "ERB", "FERB");
+    return translate_with_style_lower_and_upper_suffixes(english, WordRules::LATEST, "erb", "ferb", "ERB", "FERB");
 }
 
 ///Translates a multi-word string (including punctuation) into a custom-styled play language!
@@ -234,26 +243,127 @@ pub fn translate_with_style(english: &str, suffix_lower: &str, special_case_suff
     }
 
     return translate_with_style_lower_and_upper_suffixes (
-        english,
+        english, WordRules::LATEST,
         suffix_lower, special_case_suffix_lower, &suffix_upper, &special_case_suffix_upper
     );
 }
 
+///Translates a multi-word string (including punctuation) using the suffixes and rules of a [`Style`]!
+///
+///Use this with [`Style::with_rules()`] if you need the output to stay exactly the same across anslatortray releases.
+///
+///# Examples
+///
+///```
+///use anslatortray::{translate_with, Style, RulesVersion};
+///
+///assert_eq!(translate_with("Where's Perry?", &Style::FERB), "Erewherb's Erryperb?");
+///
+///let pinned = Style::new("ancy", "fancy").with_rules(RulesVersion::V0_5);
+///assert_eq!(translate_with("Hyphens-are-difficult-aren't-they?", &pinned), "Yphenshancy-arefancy-ifficultdancy-arenfancy't-eythancy?");
+///
+/////Older rules are kept around so output doesn't change under you
+///assert_eq!(translate_with("mcDONALD", &Style::WAY.with_rules(RulesVersion::V0_4)), "ONALDmcDAY");
+///assert_eq!(translate_with("mcDONALD", &Style::WAY.with_rules(RulesVersion::V0_5)), "ONALDmcDay");
+///```
+pub fn translate_with(english: &str, style: &Style) -> String {
+    let mut pig_latin_string_bytes = Vec::<u8>::with_capacity(english.len() * 2);//Plenty of headroom in case the words are very small or the suffixes are long
+    translate_byte_string_with(english.as_bytes(), style, &mut pig_latin_string_bytes);
+
+    return translated_string(pig_latin_string_bytes);
+}
+
+///Translates a multi-word string (including punctuation) like [`translate_with()`], and also reports words whose translation relied on a heuristic!
+///
+///Every [`Diagnostic`] holds the byte ranges of the word in both the English and Pig Latin text, so the risky spots can be highlighted for review.
+///
+///# Examples
+///
+///```
+///use anslatortray::{translate_with_diagnostics, Style, DiagnosticKind};
+///
+///let english = "The café is ISn't open";
+///let (pig_latin, diagnostics) = translate_with_diagnostics(english, &Style::WAY);
+///assert_eq!(pig_latin, "Ethay afcayé isway ISnWAY't openway");
+///
+///let kinds: Vec<DiagnosticKind> = diagnostics.iter().map(|diagnostic| diagnostic.kind).collect();
+///assert_eq!(kinds, [DiagnosticKind::NonAsciiLetter, DiagnosticKind::AmbiguousCase, DiagnosticKind::ContractionSuffix]);
+///
+///assert_eq!(&pig_latin[diagnostics[0].pig_latin.clone()], "afcay");
+///assert_eq!(&english[diagnostics[1].english.clone()], "ISn't");
+///```
+pub fn translate_with_diagnostics(english: &str, style: &Style) -> (String, Vec<Diagnostic>) {
+    let mut pig_latin_string_bytes = Vec::<u8>::with_capacity(english.len() * 2);//Plenty of headroom in case the words are very small or the suffixes are long
+    let mut diagnostics = Vec::new();
+    translate_byte_string_with_diagnostics(english.as_bytes(), style, &mut pig_latin_string_bytes, &mut diagnostics);
+
+    return (translated_string(pig_latin_string_bytes), diagnostics);
+}
+
+///Translates a multi-word string (including punctuation) into Pig Latin, only allocating if there is something to translate!
+///
+///If `english` contains no words (ex. it is just numbers, IDs, punctuation or whitespace), the translation would be identical,
+///so it is returned as [`Cow::Borrowed`]. Otherwise a buffer is allocated once the first word is found, like [`translate()`].
+///
+///# Examples
+///
+///```
+///use std::borrow::Cow;
+///use anslatortray::translate_cow;
+///
+///assert!(matches!(translate_cow("1234-5678 (42%)"), Cow::Borrowed("1234-5678 (42%)")));
+///assert!(matches!(translate_cow(""), Cow::Borrowed("")));
+///
+///let translated = translate_cow("404: Not found");
+///assert!(matches!(translated, Cow::Owned(_)));
+///assert_eq!(translated, "404: Otnay oundfay");
+///```
+pub fn translate_cow(english: &str) -> Cow<'_, str> {
+    return translate_cow_with(english, &Style::WAY);
+}
+
+///Translates a multi-word string (including punctuation) using a [`Style`] like [`translate_with()`], only allocating if there is something to translate!
+///
+///See [`translate_cow()`] for details.
+///
+///# Examples
+///
+///```
+///use std::borrow::Cow;
+///use anslatortray::{translate_cow_with, Style};
+///
+///assert!(matches!(translate_cow_with("¿¡ 🐷 !?", &Style::FERB), Cow::Borrowed(_)));
+///assert_eq!(translate_cow_with("¿Where's Perry?", &Style::FERB), "¿Erewherb's Erryperb?");
+///```
+pub fn translate_cow_with<'a>(english: &'a str, style: &Style) -> Cow<'a, str> {
+    //Every word gets a suffix, so the translation only equals the input if there are no words at all
+    let first_word_start = match english.bytes().position(|x| style.classes().is_letter(x)) {
+        Some(index) => index,
+        None => { return Cow::Borrowed(english); },
+    };
+
+    let mut pig_latin_string_bytes = Vec::<u8>::with_capacity(english.len() * 2);//Plenty of headroom in case the words are very small or the suffixes are long
+    pig_latin_string_bytes.extend_from_slice(&english.as_bytes()[..first_word_start]);
+    //There are no words before this, so translating from here is the same as translating from the start
+    translate_byte_string_with(&english.as_bytes()[first_word_start..], style, &mut pig_latin_string_bytes);
+
+    return Cow::Owned(translated_string(pig_latin_string_bytes));
+}
+
 //More efficient: Does not need to convert to upppercase at runtime
 fn translate_with_style_lower_and_upper_suffixes (
-    english: &str,
+    english: &str, rules: WordRules<'_>,
     suffix_lower: &str, special_case_suffix_lower: &str, suffix_upper: &str, special_case_suffix_upper: &str
 ) -> String {
     //Convert the string slices to byte slices and translate those (only ASCII letters are affected, non-letters or UTF-8 are preserved)
     let mut pig_latin_string_bytes = Vec::<u8>::with_capacity(english.len() * 2);//Plenty of headroom in case the words are very small or the suffixes are long
     translate_byte_string_with_style_lower_and_upper_suffixes (
-        english.as_bytes(),
+        english.as_bytes(), rules,
         suffix_lower.as_bytes(), special_case_suffix_lower.as_bytes(), suffix_upper.as_bytes(), special_case_suffix_upper.as_bytes(),
         &mut pig_latin_string_bytes
     );
 
-    //This is safe since translate_byte_string_with_style does not touch any unicode bytes (it just copies them)
-    return unsafe { String::from_utf8_unchecked(pig_latin_string_bytes) };
+    return translated_string(pig_latin_string_bytes);
 }
 
 /* Tests */
@@ -261,6 +371,9 @@ fn translate_with_style_lower_and_upper_suffixes (
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::style::RulesVersion;
+    use crate::classes::ByteClasses;
+
     const SUFFIX_SPECIAL_CASE_SUFFIX_PAIRS: [(&str, &str); 9] = [
         ("ay", "way"), ("ay", "yay"), ("ay", "hay"), ("erb", "ferb"), ("ancy", "fancy"), ("orange", "porange"), ("anana", "banana"), ("atin", "latin"), ("ust", "rust")
     ];
@@ -341,6 +454,161 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_translate_with_no_vowels() {
+        assert_eq!(translate("Hmm, shh psst!"), "Hmmay, shhay psstay!");
+        assert_eq!(translate("bcd hello"), "bcday ellohay");
+        assert_eq!(translate("TSK TSK"), "TSKAY TSKAY");
+
+        //anslatortray 0.5.0 (and so RulesVersion::V0_5) dropped these words, or took the vowel from the next word
+        assert_eq!(translate_with("Hmm, shh psst!", &Style::WAY.with_rules(RulesVersion::V0_5)), "");
+        assert_eq!(translate_with("bcd hello", &Style::WAY.with_rules(RulesVersion::V0_5)), "ellobcd hay");
+
+        assert_eq!(translate_with("Hmm, shh psst!", &Style::WAY.with_rules(RulesVersion::V0_4)), "Hmmay, shhay psstay!");
+        assert_eq!(translate_with("bcd hello", &Style::WAY.with_rules(RulesVersion::V0_4)), "bcday ellohay");
+    }
+
+    #[test]
+    fn test_translate_vowel_start_at_end() {
+        assert_eq!(translate("I am"), "Iway amway");
+        assert_eq!(translate("apple"), "appleway");
+        assert_eq!(translate("it is"), "itway isway");
+        assert_eq!(translate("Ok"), "Okway");
+        assert_eq!(translate("HELLO ALL"), "ELLOHAY ALLWAY");
+        assert_eq!(translate("ISN't IT"), "ISNWAY't ITWAY");
+        assert_eq!(translate_with("Hello, I am", &Style::FERB.with_rules(RulesVersion::V0_6)), "Elloherb, Iferb amferb");
+
+        //anslatortray 0.5.0 (and so RulesVersion::V0_5) dropped the first letter of these
+        assert_eq!(translate_with("Hello, I am", &Style::FERB.with_rules(RulesVersion::V0_5)), "Elloherb, Iferb mferb");
+        assert_eq!(translate_with("ISN't IT", &Style::WAY.with_rules(RulesVersion::V0_5)), "ISNWAY't TWAY");
+    }
+
+    #[test]
+    fn test_translate_yay_uppercase() {
+        assert_eq!(translate_yay("ISN't IT?"), "ISNYAY't ITYAY?");
+        assert_eq!(translate_yay("Isn't it?"), "Isnyay't ityay?");
+    }
+
+    #[test]
+    fn test_translate_cow() {
+        for english in ["", " ", "123", "1234-5678 (42%)", "¡¿ é 🐷 ?!", "' - '"] {
+            let translated = translate_cow(english);
+            assert!(matches!(translated, Cow::Borrowed(_)), "{:?}", english);
+            assert_eq!(translated, translate(english));
+        }
+
+        for english in ["a", "42 is the answer", "¡Hola!", "'tis", "Hyphens-are-difficult-aren't-they?'", "naïve"] {
+            let translated = translate_cow_with(english, &Style::FERB);
+            assert!(matches!(translated, Cow::Owned(_)), "{:?}", english);
+            assert_eq!(translated, translate_ferb(english));
+        }
+    }
+
+    #[test]
+    fn test_translate_with_diagnostics() {
+        use crate::diagnostics::DiagnosticKind::*;
+
+        const ENGLISH: &str = "Hmm, mcDONALD's HTML naïve résumé isn't a nice-word";
+        let (pig_latin, diagnostics) = translate_with_diagnostics(ENGLISH, &Style::WAY);
+        assert_eq!(pig_latin, translate(ENGLISH));
+
+        let found: Vec<(crate::diagnostics::DiagnosticKind, &str, &str)> = diagnostics.iter().map(|diagnostic| {
+            (diagnostic.kind, &ENGLISH[diagnostic.english.clone()], &pig_latin[diagnostic.pig_latin.clone()])
+        }).collect();
+        assert_eq!(found, [
+            (NoVowel, "Hmm", "Hmmay"),
+            (AmbiguousCase, "mcDONALD's", "ONALDmcDay's"),
+            (ContractionSuffix, "mcDONALD's", "ONALDmcDay's"),
+            (NoVowel, "HTML", "HTMLAY"),
+            (AcronymLike, "HTML", "HTMLAY"),
+            (NonAsciiLetter, "na", "anay"),
+            (NonAsciiLetter, "ve", "evay"),
+            (NonAsciiLetter, "r", "rway"),
+            (NonAsciiLetter, "sum", "umsay"),
+            (ContractionSuffix, "isn't", "isnway't"),
+        ]);
+    }
+
+    #[test]
+    fn test_translate_with_custom_classes() {
+        static CLASSES: ByteClasses = ByteClasses::new(b"aeiouw", b"'`");
+        let style = Style::WAY.with_classes(&CLASSES);
+
+        let english = "Cwm isn`t WWII's crwth, Psst";
+        let expected = "Wmcay isnway`t WWIIWAY's wthcray, Psstay";
+        assert_eq!(translate_with(english, &style), expected);
+        assert_eq!(translate_cow_with(english, &style), expected);
+        assert_eq!(crate::PigLatin(english, &style).to_string(), expected);
+        assert_eq!(translate_with(english, &Style::WAY), "Cwmay isnway`tway IIWWAY's crwthay, Psstay");
+
+        //Everything else that splits text into words uses the same classes
+        let (pig_latin, diagnostics) = translate_with_diagnostics(english, &style);
+        assert_eq!(pig_latin, expected);
+        assert!(diagnostics.iter().any(|diagnostic| (diagnostic.kind == crate::DiagnosticKind::ContractionSuffix) && (&english[diagnostic.english.clone()] == "isn`t")));
+        assert_eq!(crate::translate_with_hook(english, &style, |_| crate::Decision::Translate), expected);
+        assert_eq!(crate::translate_with_offsets(english, &style).0, expected);
+        assert_eq!(crate::translate_tokens(english, &style).map(|token| token.pig_latin().to_string()).collect::<String>(), expected);
+        #[cfg(feature = "std")]
+        assert_eq!(crate::CachingTranslator::new(style, 16).translate(english), expected);
+    }
+
+    #[test]
+    fn test_translate_with_joiners() {
+        static CLASSES: ByteClasses = ByteClasses::ENGLISH.with_joiners(b"-");
+        let style = Style::WAY.with_classes(&CLASSES);
+
+        let english = "A well-known, -odd- word isn't-it; SH-SH, x--y";
+        let expected = "Away ell-knownway, -oddway- ordway isnway't-it; SH-SHAY, xway--yway";
+        assert_eq!(translate_with(english, &style), expected);
+        assert_eq!(translate_with(english, &Style::WAY), "Away ellway-ownknay, -oddway- ordway isnway't-itway; SHAY-SHAY, xway--yway");
+
+        //Everything else that splits text into words joins them the same way
+        assert_eq!(translate_cow_with(english, &style), expected);
+        assert_eq!(crate::PigLatin(english, &style).to_string(), expected);
+        assert_eq!(translate_with_diagnostics(english, &style).0, expected);
+        assert_eq!(crate::translate_with_offsets(english, &style).0, expected);
+        assert_eq!(crate::translate_tokens(english, &style).map(|token| token.pig_latin().to_string()).collect::<String>(), expected);
+        assert!(crate::translate_tokens(english, &style).any(|token| token.english() == "well-known"));
+        #[cfg(feature = "std")]
+        assert_eq!(crate::CachingTranslator::new(style, 16).translate(english), expected);
+    }
+
+    #[test]
+    fn test_translate_with_v0_5_matches_0_5_0() {
+        //The output of anslatortray 0.5.0's translate() and translate_yay(), bugs included
+        let v0_5 = Style::WAY.with_rules(RulesVersion::V0_5);
+        assert_eq!(translate_with("psst", &v0_5), "");
+        assert_eq!(translate_with("I am", &v0_5), "Iway mway");
+        assert_eq!(translate_with("Psst, I am", &v0_5), "Ipsst, ay mway");
+        assert_eq!(translate_with("tCH eel", &v0_5), "eeltCH ay");
+        assert_eq!(translate_with("APPLE", &Style::YAY.with_rules(RulesVersion::V0_5)), "PPLEYAY");
+        assert_eq!(translate_with("ISN'T IT", &Style::YAY.with_rules(RulesVersion::V0_5)), "ISNYAY'T TYAY");
+    }
+
+    #[test]
+    fn test_translate_with_rules_versions() {
+        //These must NEVER change; add a new RulesVersion instead
+        const ENGLISH: &str = "Let's try some edge cases: ISn't THIS COOL? What ABOUT a MIX, mcDONALD? Hyphens-are-difficult-aren't-they?";
+
+        assert_eq!(translate_with(ENGLISH, &Style::WAY.with_rules(RulesVersion::V0_4)),
+            "Etlay's ytray omesay edgeway asescay: ISnway't ISTHAY OOLCAY? Atwhay ABOUTWAY away IXMAY, ONALDmcDAY? Yphenshay-areway-ifficultday-arenway't-eythay?"
+        );
+        assert_eq!(translate_with(ENGLISH, &Style::WAY.with_rules(RulesVersion::V0_5)),
+            "Etlay's ytray omesay edgeway asescay: ISnWAY't ISTHAY OOLCAY? Atwhay ABOUTWAY away IXMAY, ONALDmcDay? Yphenshay-areway-ifficultday-arenway't-eythay?"
+        );
+        assert_eq!(translate_with(ENGLISH, &Style::WAY.with_rules(RulesVersion::V0_6)), translate_with(ENGLISH, &Style::WAY.with_rules(RulesVersion::V0_5)));
+        assert_eq!(translate_with(ENGLISH, &Style::WAY), translate_with(ENGLISH, &Style::WAY.with_rules(RulesVersion::V0_6)));
+
+        //Words without a vowel are uppercase if their second letter is, even if their first letter isn't
+        assert_eq!(translate_with("tCH, sT and Psst", &Style::WAY.with_rules(RulesVersion::V0_4)), "tCHAY, sTAY andway Psstay");
+        assert_eq!(translate_with("tCH, sT and Psst", &Style::WAY.with_rules(RulesVersion::V0_6)), "tCHAY, sTAY andway Psstay");
+        assert_eq!(translate_with("tCH, sT and Psst", &Style::WAY.with_rules(RulesVersion::V0_5)), "andtCH, sT ay ");
+        assert_eq!(translate_with(ENGLISH, &Style::WAY), translate(ENGLISH));
+        assert_eq!(translate_with(ENGLISH, &Style::YAY), translate_yay(ENGLISH));
+        assert_eq!(translate_with(ENGLISH, &Style::HAY), translate_hay(ENGLISH));
+        assert_eq!(translate_with(ENGLISH, &Style::FERB), translate_ferb(ENGLISH));
+    }
 }
 
 /* Benches */
@@ -0,0 +1,289 @@
+/* style.rs
+ * By: John Jekel
+ * Copyright (C) 2022 John Jekel
+ * See the LICENSE file at the root of the project for licensing info.
+ *
+ * Translation styles (suffixes) and versioned rule sets
+ *
+*/
+
+/* Imports */
+
+use crate::classes::ByteClasses;
+
+/* Constants */
+
+//The longest suffix (in bytes) a Style can hold; this lets Styles live on the stack without allocating
+const MAX_SUFFIX_LEN: usize = 32;
+
+/* Types */
+
+///Selects which set of heuristics is used to translate each word.
+///
+///The heuristics used by anslatortray change from time to time as edge cases are improved.
+///If you store or compare translated text (ex. snapshot tests), pin a specific version so that upgrading anslatortray never changes your output.
+///New heuristics are only ever added as new versions; the behaviour of an existing version is never changed.
+///
+///# Examples
+///
+///```
+///# #[cfg(feature = "alloc")] {
+///use anslatortray::{translate_with, Style, RulesVersion};
+///
+///let pinned = Style::WAY.with_rules(RulesVersion::V0_6);
+///assert_eq!(translate_with("Hello world!", &pinned), "Ellohay orldway!");
+///
+/////The versions differ in how they decide whether a word is uppercase
+///assert_eq!(translate_with("ISn't it?", &Style::WAY.with_rules(RulesVersion::V0_4)), "ISnway't itway?");
+///assert_eq!(translate_with("ISn't it?", &Style::WAY.with_rules(RulesVersion::V0_5)), "ISnWAY't itway?");
+///
+/////And in what they do with words without vowels
+///assert_eq!(translate_with("Psst, I am", &Style::WAY.with_rules(RulesVersion::V0_5)), "Ipsst, ay mway");
+///assert_eq!(translate_with("Psst, I am", &Style::WAY.with_rules(RulesVersion::V0_6)), "Psstay, Iway amway");
+///# }
+///```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Default)]
+#[non_exhaustive]
+pub enum RulesVersion {
+    ///The word-at-a-time heuristics from anslatortray 0.4.
+    ///
+    ///A word is considered uppercase if its last letter is.
+    V0_4,
+    ///The single-pass heuristics introduced in anslatortray 0.5.
+    ///
+    ///A word is considered uppercase if its first two letters are (or just its second letter, if it starts with a vowel or has none).
+    ///
+    ///Translating whole strings with these rules reproduces the output of anslatortray 0.5.0 exactly, bugs included:
+    ///a word without a vowel (ex. "psst") is rotated up to the first vowel in the text after it (or dropped along with the rest of the text if there isn't one),
+    ///and a word starting with a vowel at the very end of the text loses its first letter ("I am" is "Iway mway").
+    ///Translating a word at a time (ex. when streaming, or with a custom tokenizer) can't reach into the following text, so there those words are translated like [`RulesVersion::V0_6`] does.
+    V0_5,
+    ///The heuristics from [`RulesVersion::V0_5`], with the bugs anslatortray 0.5.0 had fixed.
+    ///
+    ///* Words without a vowel (ex. "psst") are kept and get the normal suffix.
+    ///* A word starting with a vowel at the very end of the text keeps its first letter ("I am" is "Iway amway").
+    ///* [`crate::byte_string::translate_yay()`] appends "YAY" (not "WAY") to uppercase words starting with a vowel, like [`crate::translate_yay()`] always has.
+    V0_6,
+    ///Always the newest rules this version of anslatortray knows about (currently [`RulesVersion::V0_6`]).
+    ///
+    ///This is what all of the translate functions that don't take a [`Style`] use.
+    #[default]
+    Latest,
+}
+
+///The suffixes and rules to use when translating.
+///
+///Styles are small, never allocate, and can be created in `const` contexts.
+///
+///# Examples
+///
+///```
+///# #[cfg(feature = "alloc")] {
+///use anslatortray::{translate_with, Style};
+///
+///const FANCY: Style = Style::new("ancy", "fancy");
+///
+///assert_eq!(translate_with("Hello world from the coolest Pig Latin translator!", &FANCY),
+///    "Ellohancy orldwancy omfrancy ethancy oolestcancy Igpancy Atinlancy anslatortrancy!"
+///);
+///assert_eq!(translate_with("HELLO WORLD!", &FANCY), "ELLOHANCY ORLDWANCY!");
+///# }
+///```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Style {
+    suffix_lower: Suffix,
+    special_case_suffix_lower: Suffix,
+    suffix_upper: Suffix,
+    special_case_suffix_upper: Suffix,
+    rules: RulesVersion,
+    classes: &'static ByteClasses,
+}
+
+//Everything a Style decides about how a word is translated besides its suffixes
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct WordRules<'a> {
+    pub(crate) version: RulesVersion,
+    pub(crate) classes: &'a ByteClasses,
+}
+
+//A suffix stored inline (so Styles don't need to allocate)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+struct Suffix {
+    bytes: [u8; MAX_SUFFIX_LEN],
+    len: usize,
+}
+
+/* Associated Functions and Methods */
+
+impl RulesVersion {
+    ///Returns the concrete version these rules refer to (resolving [`RulesVersion::Latest`]).
+    ///
+    ///# Examples
+    ///
+    ///```
+    ///use anslatortray::RulesVersion;
+    ///
+    ///assert_eq!(RulesVersion::Latest.resolve(), RulesVersion::V0_6);
+    ///assert_eq!(RulesVersion::V0_4.resolve(), RulesVersion::V0_4);
+    ///```
+    pub const fn resolve(self) -> RulesVersion {
+        return match self {
+            RulesVersion::Latest => RulesVersion::V0_6,
+            version => version,
+        };
+    }
+}
+
+impl Style {
+    ///The default style: "ay" and "way" (see [`crate::translate_way()`]).
+    pub const WAY: Style = Style::new("ay", "way");
+    ///The "ay" and "yay" style (see [`crate::translate_yay()`]).
+    pub const YAY: Style = Style::new("ay", "yay");
+    ///The "ay" and "hay" style (see [`crate::translate_hay()`]).
+    pub const HAY: Style = Style::new("ay", "hay");
+    ///Ferb Latin: "erb" and "ferb" (see [`crate::translate_ferb()`]).
+    pub const FERB: Style = Style::new("erb", "ferb");
+
+    ///Creates a new style with the suffix you wish to have appended to most words, and the suffix
+    ///you wish to have appended in various special-cases (such as when a word is only one letter or starts with a vowel).
+    ///
+    ///Uses [`RulesVersion::Latest`] and [`ByteClasses::ENGLISH`]; see [`Style::with_rules()`] to pin a specific version and [`Style::with_classes()`] to use other vowels or apostrophes.
+    ///
+    ///Note: The suffixes must be entirely lower-case or weird results may occur.
+    ///
+    ///# Panics
+    ///
+    ///Panics if either suffix is longer than 32 bytes.
+    pub const fn new(suffix: &str, special_case_suffix: &str) -> Style {
+        return Style {
+            suffix_lower: Suffix::new(suffix.as_bytes(), false),
+            special_case_suffix_lower: Suffix::new(special_case_suffix.as_bytes(), false),
+            suffix_upper: Suffix::new(suffix.as_bytes(), true),
+            special_case_suffix_upper: Suffix::new(special_case_suffix.as_bytes(), true),
+            rules: RulesVersion::Latest,
+            classes: &ByteClasses::ENGLISH,
+        };
+    }
+
+    ///Returns a copy of this style that uses the given [`RulesVersion`].
+    pub const fn with_rules(mut self, rules: RulesVersion) -> Style {
+        self.rules = rules;
+        return self;
+    }
+
+    ///Returns the [`RulesVersion`] this style uses.
+    pub const fn rules(&self) -> RulesVersion {
+        return self.rules;
+    }
+
+    ///Returns a copy of this style that uses the given [`ByteClasses`] to decide which bytes are vowels and apostrophes.
+    pub const fn with_classes(mut self, classes: &'static ByteClasses) -> Style {
+        self.classes = classes;
+        return self;
+    }
+
+    ///Returns the [`ByteClasses`] this style uses.
+    #[inline(always)]
+    pub const fn classes(&self) -> &'static ByteClasses {
+        return self.classes;
+    }
+
+    #[inline(always)]
+    pub(crate) const fn word_rules(&self) -> WordRules<'static> {
+        return WordRules { version: self.rules, classes: self.classes };
+    }
+
+    pub(crate) fn suffix_lower(&self) -> &[u8] {
+        return self.suffix_lower.as_bytes();
+    }
+
+    pub(crate) fn special_case_suffix_lower(&self) -> &[u8] {
+        return self.special_case_suffix_lower.as_bytes();
+    }
+
+    pub(crate) fn suffix_upper(&self) -> &[u8] {
+        return self.suffix_upper.as_bytes();
+    }
+
+    pub(crate) fn special_case_suffix_upper(&self) -> &[u8] {
+        return self.special_case_suffix_upper.as_bytes();
+    }
+}
+
+impl WordRules<'static> {
+    //The rules of the translate functions that don't take a Style
+    #[cfg(feature = "alloc")]
+    pub(crate) const LATEST: WordRules<'static> = WordRules { version: RulesVersion::Latest, classes: &ByteClasses::ENGLISH };
+}
+
+impl Default for Style {
+    fn default() -> Style {
+        return Style::WAY;
+    }
+}
+
+impl Suffix {
+    const fn new(suffix: &[u8], uppercase: bool) -> Suffix {
+        if suffix.len() > MAX_SUFFIX_LEN {
+            panic!("anslatortray suffixes may be at most 32 bytes long");
+        }
+
+        let mut bytes = [0u8; MAX_SUFFIX_LEN];
+        let mut index: usize = 0;
+        while index < suffix.len() {
+            //NOTE: We can't use fast_to_ascii_uppercase in case the suffixes contain UTF-8 or non-letters
+            bytes[index] = if uppercase { suffix[index].to_ascii_uppercase() } else { suffix[index] };
+            index += 1;
+        }
+
+        return Suffix { bytes, len: suffix.len() };
+    }
+
+    fn as_bytes(&self) -> &[u8] {
+        return &self.bytes[..self.len];
+    }
+}
+
+/* Tests */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_style_suffixes() {
+        assert_eq!(Style::WAY.suffix_lower(), b"ay");
+        assert_eq!(Style::WAY.special_case_suffix_lower(), b"way");
+        assert_eq!(Style::WAY.suffix_upper(), b"AY");
+        assert_eq!(Style::WAY.special_case_suffix_upper(), b"WAY");
+
+        assert_eq!(Style::YAY.special_case_suffix_upper(), b"YAY");
+        assert_eq!(Style::FERB.suffix_upper(), b"ERB");
+
+        let utf8 = Style::new("äy", "wäy");
+        assert_eq!(utf8.suffix_lower(), "äy".as_bytes());
+        assert_eq!(utf8.special_case_suffix_upper(), "WäY".as_bytes());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_style_suffix_too_long() {
+        Style::new("ay", "abcdefghijklmnopqrstuvwxyzabcdefghijklmnopqrstuvwxyz");
+    }
+
+    #[test]
+    fn test_rules_version() {
+        assert_eq!(RulesVersion::default(), RulesVersion::Latest);
+        assert_eq!(RulesVersion::Latest.resolve(), RulesVersion::V0_6);
+        assert_eq!(Style::default().rules(), RulesVersion::Latest);
+        assert_eq!(Style::WAY.with_rules(RulesVersion::V0_4).rules(), RulesVersion::V0_4);
+    }
+
+    #[test]
+    fn test_style_classes() {
+        static CLASSES: ByteClasses = ByteClasses::new(b"aeiouw", b"'");
+        assert_eq!(Style::WAY.classes(), &ByteClasses::ENGLISH);
+        assert_eq!(Style::WAY.with_classes(&CLASSES).classes(), &CLASSES);
+        assert_ne!(Style::WAY.with_classes(&CLASSES), Style::WAY);
+    }
+}
@@ -0,0 +1,252 @@
+/* tokenizer.rs
+ * By: John Jekel
+ * Copyright (C) 2022 John Jekel
+ * See the LICENSE file at the root of the project for licensing info.
+ *
+ * Pluggable rules for splitting text into words and the separators in-between them
+ *
+*/
+
+/* Imports */
+
+use core::ops::Range;
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+use crate::spans::{self, SpanKind};
+use crate::classes::ByteClasses;
+#[cfg(feature = "alloc")]
+use crate::byte_string::translate_word_with;
+#[cfg(feature = "alloc")]
+use crate::style::Style;
+#[cfg(feature = "alloc")]
+use crate::utf8::translated_string;
+
+/* Types */
+
+///A piece of text found by a [`Tokenizer`], as a byte range into the text.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Segment {
+    ///A word, which is translated
+    Word(Range<usize>),
+    ///Anything else (whitespace, punctuation, contraction suffixes, etc.), which is copied as-is
+    Separator(Range<usize>),
+}
+
+///Splits text into words and the separators in-between them; see [`translate_with_tokenizer()`].
+///
+///The segments must be in order, cover the whole text without gaps or overlaps, and start and end on [`char`] boundaries.
+///Words may contain anything (digits, underscores, non-ASCII letters, etc.): the usual rules are applied to them,
+///with the onset being everything before the first ASCII vowel (or y after the first character).
+///
+///# Examples
+///
+///```
+///use anslatortray::{Tokenizer, Segment, WordCharTokenizer, Style};
+///
+/////Treat snake_case identifiers and numbers as part of words
+///let identifiers = WordCharTokenizer(|c: char| c.is_ascii_alphanumeric() || (c == '_'));
+///# #[cfg(feature = "alloc")]
+///# use anslatortray::translate_with_tokenizer;
+///# #[cfg(feature = "alloc")]
+///assert_eq!(translate_with_tokenizer("call my_function2(x)", &Style::WAY, &identifiers), "allcay y_function2may(xway)");
+///
+///let segments: Vec<Segment> = identifiers.segments("my_function2(x)").collect();
+///assert_eq!(segments, [Segment::Word(0..12), Segment::Separator(12..13), Segment::Word(13..14), Segment::Separator(14..15)]);
+///```
+pub trait Tokenizer {
+    ///The iterator over the segments of a text.
+    type Segments<'a>: Iterator<Item = Segment> where Self: 'a;
+
+    ///Returns an iterator over the segments of `english`, in order.
+    fn segments<'a>(&'a self, english: &'a str) -> Self::Segments<'a>;
+}
+
+///The [`Tokenizer`] every other translation function uses: words are runs of ASCII letters (and joiners between them),
+///and an apostrophe followed by letters directly after a word (ex. the 't in "isn't") is a separator.
+///
+///Which bytes are apostrophes and joiners comes from its [`ByteClasses`] ([`ByteClasses::ENGLISH`] unless changed with [`DefaultTokenizer::with_classes()`]),
+///so use the same ones as your [`crate::Style`] to split text exactly like [`crate::translate_with()`] does.
+///
+///# Examples
+///
+///```
+///use anslatortray::{Tokenizer, Segment, DefaultTokenizer, ByteClasses};
+///
+///static BACKTICKS: ByteClasses = ByteClasses::new(b"aeiou", b"`");
+///let tokenizer = DefaultTokenizer::new().with_classes(&BACKTICKS);
+///
+///let segments: Vec<Segment> = tokenizer.segments("isn`t it").collect();
+///assert_eq!(segments, [Segment::Word(0..3), Segment::Separator(3..5), Segment::Separator(5..6), Segment::Word(6..8)]);
+///```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DefaultTokenizer {
+    classes: &'static ByteClasses,
+}
+
+///The segments of a text found by the [`DefaultTokenizer`].
+#[derive(Clone)]
+pub struct DefaultSegments<'a>(spans::Spans<'a>);
+
+///A [`Tokenizer`] whose words are runs of [`char`]s for which the function returns true.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct WordCharTokenizer<F: Fn(char) -> bool>(pub F);
+
+///The segments of a text found by a [`WordCharTokenizer`].
+#[derive(Clone)]
+pub struct WordCharSegments<'a, F: Fn(char) -> bool> {
+    english: &'a str,
+    index: usize,
+    is_word_char: &'a F,
+}
+
+/* Associated Functions and Methods */
+
+impl DefaultTokenizer {
+    ///Creates a tokenizer that uses [`ByteClasses::ENGLISH`].
+    pub const fn new() -> DefaultTokenizer {
+        return DefaultTokenizer { classes: &ByteClasses::ENGLISH };
+    }
+
+    ///Returns a copy of this tokenizer that uses the given [`ByteClasses`] to decide which bytes are apostrophes and joiners.
+    pub const fn with_classes(mut self, classes: &'static ByteClasses) -> DefaultTokenizer {
+        self.classes = classes;
+        return self;
+    }
+
+    ///Returns the [`ByteClasses`] this tokenizer uses.
+    pub const fn classes(&self) -> &'static ByteClasses {
+        return self.classes;
+    }
+}
+
+impl Default for DefaultTokenizer {
+    fn default() -> DefaultTokenizer {
+        return DefaultTokenizer::new();
+    }
+}
+
+impl Tokenizer for DefaultTokenizer {
+    type Segments<'a> = DefaultSegments<'a>;
+
+    fn segments<'a>(&'a self, english: &'a str) -> DefaultSegments<'a> {
+        return DefaultSegments(spans::Spans::new(english.as_bytes(), self.classes));
+    }
+}
+
+impl Iterator for DefaultSegments<'_> {
+    type Item = Segment;
+
+    fn next(&mut self) -> Option<Segment> {
+        let span = self.0.next()?;
+        return Some(if span.kind == SpanKind::Word { Segment::Word(span.range) } else { Segment::Separator(span.range) });
+    }
+}
+
+impl<F: Fn(char) -> bool> Tokenizer for WordCharTokenizer<F> {
+    type Segments<'a> = WordCharSegments<'a, F> where F: 'a;
+
+    fn segments<'a>(&'a self, english: &'a str) -> WordCharSegments<'a, F> {
+        return WordCharSegments { english, index: 0, is_word_char: &self.0 };
+    }
+}
+
+impl<F: Fn(char) -> bool> Iterator for WordCharSegments<'_, F> {
+    type Item = Segment;
+
+    fn next(&mut self) -> Option<Segment> {
+        let start = self.index;
+        let is_word = (self.is_word_char)(self.english[start..].chars().next()?);
+
+        self.index = match self.english[start..].char_indices().find(|&(_, c)| (self.is_word_char)(c) != is_word) {
+            Some((length, _)) => start + length,
+            None => self.english.len(),
+        };
+        return Some(if is_word { Segment::Word(start..self.index) } else { Segment::Separator(start..self.index) });
+    }
+}
+
+/* Functions */
+
+///Translates a multi-word string (including punctuation) using a [`Style`], with the words found by a [`Tokenizer`]!
+///
+///With a [`DefaultTokenizer`] using the style's [`ByteClasses`], this is the same as [`crate::translate_with()`].
+///
+///# Panics
+///
+///Panics if the tokenizer returns a segment that doesn't start and end on [`char`] boundaries.
+///
+///# Examples
+///
+///```
+///use anslatortray::{translate_with_tokenizer, DefaultTokenizer, WordCharTokenizer, Style};
+///
+///assert_eq!(translate_with_tokenizer("Where's Perry?", &Style::FERB, &DefaultTokenizer::new()), "Erewherb's Erryperb?");
+///
+/////Keep hyphenated words together
+///let hyphenated = WordCharTokenizer(|c: char| c.is_ascii_alphabetic() || (c == '-'));
+///assert_eq!(translate_with_tokenizer("a well-known fact", &Style::WAY, &hyphenated), "away ell-knownway actfay");
+///```
+#[cfg(feature = "alloc")]
+pub fn translate_with_tokenizer<T: Tokenizer>(english: &str, style: &Style, tokenizer: &T) -> String {
+    let mut pig_latin_string_bytes = Vec::<u8>::with_capacity(english.len() * 2);//Plenty of headroom in case the words are very small or the suffixes are long
+
+    for segment in tokenizer.segments(english) {
+        match segment {
+            Segment::Word(range) if !range.is_empty() => {
+                //The onset always ends at an ASCII vowel, so rotating the word never splits a char
+                translate_word_with(english[range].as_bytes(), style, &mut pig_latin_string_bytes);
+            },
+            Segment::Word(range) | Segment::Separator(range) => {
+                pig_latin_string_bytes.extend_from_slice(english[range].as_bytes());
+            },
+        }
+    }
+
+    return translated_string(pig_latin_string_bytes);
+}
+
+/* Tests */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "alloc")]
+    use crate::style::RulesVersion;
+
+    #[cfg(feature = "alloc")]
+    const ENGLISH: &str = "Let's try some edge cases: ISn't THIS COOL? naïve mcDONALD's, bcd 123 Hyphens-are-difficult-aren't-they?' A";
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_default_tokenizer_matches_translate_with() {
+        static CUSTOM: ByteClasses = ByteClasses::new(b"aeiouw", b"`").with_joiners(b"-");
+        for style in [Style::WAY, Style::FERB, Style::new("äy", "wäy"), Style::HAY.with_rules(RulesVersion::V0_4), Style::WAY.with_classes(&CUSTOM)] {
+            let tokenizer = DefaultTokenizer::new().with_classes(style.classes());
+            assert_eq!(translate_with_tokenizer(ENGLISH, &style, &tokenizer), crate::translate_with(ENGLISH, &style));
+            assert_eq!(translate_with_tokenizer("Cwm isn`t it, well-known", &style, &tokenizer), crate::translate_with("Cwm isn`t it, well-known", &style));
+        }
+        assert_eq!(DefaultTokenizer::default(), DefaultTokenizer::new());
+        assert_eq!(DefaultTokenizer::new().classes(), &ByteClasses::ENGLISH);
+    }
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn test_translate_with_word_char_tokenizer() {
+        //Words made of anything at all still translate to valid text
+        let everything = WordCharTokenizer(|c: char| !c.is_whitespace());
+        assert_eq!(translate_with_tokenizer("1st naïve QUÉBEC 🐷pig", &Style::WAY, &everything), "1stay aïvenay UÉBECQAY ig🐷pay");
+        assert_eq!(translate_with_tokenizer("4u", &Style::WAY, &everything), "u4ay");
+        assert_eq!(translate_with_tokenizer("1st 4u Q1a", &Style::WAY.with_rules(RulesVersion::V0_4), &everything), "1stay u4ay Aq1ay");
+    }
+
+    #[test]
+    fn test_word_char_tokenizer() {
+        let digits = WordCharTokenizer(|c: char| c.is_ascii_digit());
+        assert_eq!(digits.segments("").count(), 0);
+        assert_eq!(digits.segments("a12b").collect::<Vec<_>>(), [Segment::Separator(0..1), Segment::Word(1..3), Segment::Separator(3..4)]);
+    }
+}
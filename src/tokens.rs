@@ -0,0 +1,156 @@
+/* tokens.rs
+ * By: John Jekel
+ * Copyright (C) 2022 John Jekel
+ * See the LICENSE file at the root of the project for licensing info.
+ *
+ * An iterator over translated words and the text in-between them
+ *
+*/
+
+/* Imports */
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::byte_string::{analyze_word, translate_with as translate_byte_string_with};
+use crate::spans::{Spans, SpanKind};
+use crate::style::Style;
+use crate::utf8::translated_string;
+use crate::word::CaseClass;
+
+/* Types */
+
+///A piece of translated text; see [`translate_tokens()`].
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Token<'a> {
+    ///A word that was translated
+    Word {
+        ///The original English word
+        english: &'a str,
+        ///The translated word
+        pig_latin: String,
+        ///How the capitalization of the word was interpreted
+        case: CaseClass,
+    },
+    ///The suffix of a contraction (ex. the 't in "isn't"), which is never translated
+    ContractionSuffix(&'a str),
+    ///Whitespace, punctuation, numbers and anything else in-between words, which is never translated
+    Separator(&'a str),
+}
+
+///An iterator over the [`Token`]s of a translation; see [`translate_tokens()`].
+#[derive(Clone)]
+pub struct Tokens<'a> {
+    english: &'a str,
+    style: &'a Style,
+    spans: Spans<'a>,
+}
+
+/* Associated Functions and Methods */
+
+impl<'a> Token<'a> {
+    ///Returns the text this token contributes to the translation.
+    pub fn pig_latin(&self) -> &str {
+        return match self {
+            Token::Word { pig_latin, .. } => pig_latin,
+            Token::ContractionSuffix(text) | Token::Separator(text) => text,
+        };
+    }
+
+    ///Returns the text this token came from in the original English.
+    pub fn english(&self) -> &'a str {
+        return match self {
+            Token::Word { english, .. } => english,
+            Token::ContractionSuffix(text) | Token::Separator(text) => text,
+        };
+    }
+}
+
+impl<'a> Iterator for Tokens<'a> {
+    type Item = Token<'a>;
+
+    fn next(&mut self) -> Option<Token<'a>> {
+        let span = self.spans.next()?;
+
+        //Spans always start and end next to ASCII letters (or the ends of the string), so they lie on char boundaries
+        let text = &self.english[span.range];
+
+        return Some(match span.kind {
+            SpanKind::Separator => Token::Separator(text),
+            SpanKind::ContractionSuffix => Token::ContractionSuffix(text),
+            SpanKind::Word => {
+                let mut pig_latin = Vec::<u8>::with_capacity(text.len() * 2);
+                translate_byte_string_with(text.as_bytes(), self.style, &mut pig_latin);
+
+                Token::Word {
+                    english: text,
+                    pig_latin: translated_string(pig_latin),
+                    case: analyze_word(text.as_bytes(), self.style.word_rules()).case,
+                }
+            },
+        });
+    }
+}
+
+/* Functions */
+
+///Translates a multi-word string (including punctuation) into a stream of [`Token`]s rather than a single [`String`]!
+///
+///Useful if you want to restyle, count or highlight words: the translator decides what counts as a word, so you don't have to.
+///Separators and contraction suffixes borrow from `english`, so only translated words are allocated.
+///Concatenating [`Token::pig_latin()`] for every token gives the same result as [`crate::translate_with()`].
+///
+///# Examples
+///
+///```
+///use anslatortray::{translate_tokens, Style, Token, CaseClass};
+///
+///let mut tokens = translate_tokens("Hello, isn't it?", &Style::WAY);
+///
+///assert_eq!(tokens.next(), Some(Token::Word { english: "Hello", pig_latin: "Ellohay".to_string(), case: CaseClass::Title }));
+///assert_eq!(tokens.next(), Some(Token::Separator(", ")));
+///assert_eq!(tokens.next(), Some(Token::Word { english: "isn", pig_latin: "isnway".to_string(), case: CaseClass::Lower }));
+///assert_eq!(tokens.next(), Some(Token::ContractionSuffix("'t")));
+///assert_eq!(tokens.next(), Some(Token::Separator(" ")));
+///assert_eq!(tokens.next(), Some(Token::Word { english: "it", pig_latin: "itway".to_string(), case: CaseClass::Lower }));
+///assert_eq!(tokens.next(), Some(Token::Separator("?")));
+///assert_eq!(tokens.next(), None);
+///
+/////Count the words in a translation
+///assert_eq!(translate_tokens("One, two, three!", &Style::WAY).filter(|token| matches!(token, Token::Word { .. })).count(), 3);
+///```
+pub fn translate_tokens<'a>(english: &'a str, style: &'a Style) -> Tokens<'a> {
+    return Tokens { english, style, spans: Spans::new(english.as_bytes(), style.classes()) };
+}
+
+/* Tests */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::style::RulesVersion;
+
+    #[test]
+    fn test_translate_tokens_matches_translate_with() {
+        const ENGLISH: &str = "Let's try some edge cases: ISn't THIS COOL? naïve mcDONALD's, bcd 123 Hyphens-are-difficult-aren't-they?'";
+
+        for style in [Style::WAY, Style::YAY, Style::FERB, Style::new("ancy", "fancy"), Style::HAY.with_rules(RulesVersion::V0_4)] {
+            let joined: String = translate_tokens(ENGLISH, &style).map(|token| token.pig_latin().to_string()).collect();
+            assert_eq!(joined, crate::translate_with(ENGLISH, &style));
+
+            let english: String = translate_tokens(ENGLISH, &style).map(|token| token.english()).collect();
+            assert_eq!(english, ENGLISH);
+        }
+    }
+
+    #[test]
+    fn test_translate_tokens_separators_borrow_input() {
+        const ENGLISH: &str = "  42 ";
+
+        let tokens: Vec<Token> = translate_tokens(ENGLISH, &Style::WAY).collect();
+        assert_eq!(tokens, [Token::Separator("  42 ")]);
+        assert_eq!(tokens[0].english().as_ptr(), ENGLISH.as_ptr());
+
+        assert_eq!(translate_tokens("", &Style::WAY).next(), None);
+    }
+}
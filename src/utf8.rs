@@ -0,0 +1,66 @@
+/* utf8.rs
+ * By: John Jekel
+ * Copyright (C) 2022 John Jekel
+ * See the LICENSE file at the root of the project for licensing info.
+ *
+ * Turns translated bytes back into strs (checking them only if the safe feature is enabled)
+ *
+*/
+
+/* Imports */
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+/* Functions */
+
+//Translation never touches any unicode bytes (it just copies them), ASCII letters are only ever rearranged or recased,
+//and the suffixes came from &strs, so translating valid UTF-8 always gives valid UTF-8.
+//With the safe feature this is double-checked anyways, panicking instead of causing undefined behaviour if it were ever broken.
+#[inline(always)]
+pub(crate) fn translated_str(bytes: &[u8]) -> &str {
+    #[cfg(feature = "safe")]
+    {
+        return core::str::from_utf8(bytes).expect("translating valid UTF-8 should always give valid UTF-8");
+    }
+    #[cfg(not(feature = "safe"))]
+    {
+        //This is safe for the reasons above
+        return unsafe { core::str::from_utf8_unchecked(bytes) };
+    }
+}
+
+//The same as translated_str(), but for an owned buffer
+#[cfg(feature = "alloc")]
+#[inline(always)]
+pub(crate) fn translated_string(bytes: Vec<u8>) -> String {
+    #[cfg(feature = "safe")]
+    {
+        return String::from_utf8(bytes).expect("translating valid UTF-8 should always give valid UTF-8");
+    }
+    #[cfg(not(feature = "safe"))]
+    {
+        //This is safe for the reasons above translated_str()
+        return unsafe { String::from_utf8_unchecked(bytes) };
+    }
+}
+
+/* Tests */
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_translated_str() {
+        let english = "Hello naïve 🐷!";
+        let mut buffer = [0u8; 64];
+        let len = crate::byte_string::translate_to_slice(english.as_bytes(), &crate::Style::new("äy", "wäy"), &mut buffer).unwrap();
+
+        assert_eq!(translated_str(&buffer[..len]), "Ellohäy anäyïeväy 🐷!");
+        #[cfg(feature = "alloc")]
+        assert_eq!(translated_string(buffer[..len].to_vec()), "Ellohäy anäyïeväy 🐷!");
+    }
+}
@@ -0,0 +1,216 @@
+/* word.rs
+ * By: John Jekel
+ * Copyright (C) 2022 John Jekel
+ * See the LICENSE file at the root of the project for licensing info.
+ *
+ * The decisions made when translating a single word, and a way to inspect them
+ *
+*/
+
+/* Imports */
+
+use crate::style::Style;
+use crate::utf8::translated_str;
+
+#[cfg(feature = "alloc")]
+use alloc::string::String;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+#[cfg(feature = "alloc")]
+use crate::byte_string::{analyze_word, translate_with as translate_byte_string_with};
+#[cfg(feature = "alloc")]
+use crate::utf8::translated_string;
+#[cfg(feature = "alloc")]
+use crate::scan::find_non_letter;
+
+/* Types */
+
+///Which rule was used to translate a word.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Rule {
+    ///The word is only one letter long, so it is left as-is and the special case suffix is added (ex. "a" becomes "away").
+    OneLetter,
+    ///The word starts with a vowel, so it is left as-is and the special case suffix is added (ex. "egg" becomes "eggway").
+    VowelStart,
+    ///The consonants before the first vowel are moved to the end and the suffix is added (ex. "String" becomes "Ingstray").
+    ConsonantCluster,
+    ///The word has no vowels, so it is left as-is and the suffix is added (ex. "psst" becomes "psstay").
+    NoVowel,
+}
+
+///How the capitalization of a word was interpreted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum CaseClass {
+    ///The word is lowercase (ex. "hello").
+    Lower,
+    ///Only the first letter of the word is capitalized (ex. "Hello").
+    Title,
+    ///The word is uppercase (ex. "HELLO"), so the suffix is uppercase too.
+    Upper,
+}
+
+///A breakdown of how a single word is translated; see [`explain_word()`].
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct WordExplanation<'a> {
+    ///The consonants before the first vowel, which are moved to the end of the word (empty unless the rule is [`Rule::ConsonantCluster`] or [`Rule::NoVowel`])
+    pub onset: &'a str,
+    ///The first vowel and everything after it
+    pub rime: &'a str,
+    ///Which rule was used
+    pub rule: Rule,
+    ///How the capitalization of the word was interpreted
+    pub case: CaseClass,
+    ///The suffix that was added
+    pub suffix: &'a str,
+    ///The translated word
+    pub pig_latin: String,
+}
+
+//The decisions made when translating a word, shared between the translation loop and explain_word()
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct WordAnalysis {
+    pub(crate) rule: Rule,
+    pub(crate) case: CaseClass,
+    //The index of the first vowel (the length of the word if there isn't one, or 0 if the onset isn't moved)
+    pub(crate) onset_len: usize,
+    //Whether the first vowel should be capitalized once moved to the start of the word
+    pub(crate) capitalize_rime: bool,
+    //Whether the first letter should be lowercased once moved to the end of the word
+    pub(crate) lowercase_onset: bool,
+}
+
+/* Associated Functions and Methods */
+
+impl WordAnalysis {
+    //Returns which of the style's suffixes is added to the word
+    pub(crate) fn suffix<'s>(&self, style: &'s Style) -> &'s str {
+        let suffix = match (self.rule, self.case) {
+            (Rule::OneLetter, _) => style.special_case_suffix_lower(),
+            (Rule::VowelStart, CaseClass::Upper) => style.special_case_suffix_upper(),
+            (Rule::VowelStart, _) => style.special_case_suffix_lower(),
+            (_, CaseClass::Upper) => style.suffix_upper(),
+            (_, _) => style.suffix_lower(),
+        };
+
+        //The suffixes came from &strs, and converting ASCII letters to uppercase keeps them valid UTF-8
+        return translated_str(suffix);
+    }
+}
+
+/* Functions */
+
+///Explains, step by step, how a single word is translated into Pig Latin using a [`Style`].
+///
+///Returns [`None`] if `word` isn't a single word (it must be non-empty and made only of ASCII letters, and any of the style's joiners between them).
+///
+///# Examples
+///
+///```
+///use anslatortray::{explain_word, Style, Rule, CaseClass};
+///
+///let explanation = explain_word("String", &Style::WAY).unwrap();
+///assert_eq!(explanation.onset, "Str");
+///assert_eq!(explanation.rime, "ing");
+///assert_eq!(explanation.rule, Rule::ConsonantCluster);
+///assert_eq!(explanation.case, CaseClass::Title);
+///assert_eq!(explanation.suffix, "ay");
+///assert_eq!(explanation.pig_latin, "Ingstray");
+///
+///let explanation = explain_word("EGG", &Style::YAY).unwrap();
+///assert_eq!(explanation.rule, Rule::VowelStart);
+///assert_eq!(explanation.case, CaseClass::Upper);
+///assert_eq!(explanation.suffix, "YAY");
+///assert_eq!(explanation.pig_latin, "EGGYAY");
+///
+///assert!(explain_word("isn't", &Style::WAY).is_none());
+///```
+#[cfg(feature = "alloc")]
+pub fn explain_word<'a>(word: &'a str, style: &'a Style) -> Option<WordExplanation<'a>> {
+    let classes = style.classes();
+    if word.is_empty() || !classes.is_letter(word.as_bytes()[0]) || find_non_letter(&word.as_bytes()[1..], classes).is_some() {
+        return None;
+    }
+
+    let analysis = analyze_word(word.as_bytes(), style.word_rules());
+
+    let suffix = analysis.suffix(style);
+
+    let mut pig_latin = Vec::<u8>::with_capacity(word.len() + suffix.len());
+    translate_byte_string_with(word.as_bytes(), style, &mut pig_latin);
+
+    return Some(WordExplanation {
+        onset: &word[..analysis.onset_len],
+        rime: &word[analysis.onset_len..],
+        rule: analysis.rule,
+        case: analysis.case,
+        suffix,
+        pig_latin: translated_string(pig_latin),
+    });
+}
+
+/* Tests */
+
+#[cfg(test)]
+#[cfg(feature = "alloc")]//explain_word() needs alloc
+mod tests {
+    use super::*;
+    use crate::style::RulesVersion;
+
+    #[test]
+    fn test_explain_word() {
+        //(word, onset, rime, rule, case, suffix)
+        let expected = [
+            ("a", "", "a", Rule::OneLetter, CaseClass::Lower, "way"),
+            ("I", "", "I", Rule::OneLetter, CaseClass::Title, "way"),
+            ("egg", "", "egg", Rule::VowelStart, CaseClass::Lower, "way"),
+            ("Egg", "", "Egg", Rule::VowelStart, CaseClass::Title, "way"),
+            ("iPHONE", "", "iPHONE", Rule::VowelStart, CaseClass::Upper, "WAY"),
+            ("try", "tr", "y", Rule::ConsonantCluster, CaseClass::Lower, "ay"),
+            ("String", "Str", "ing", Rule::ConsonantCluster, CaseClass::Title, "ay"),
+            ("STRING", "STR", "ING", Rule::ConsonantCluster, CaseClass::Upper, "AY"),
+            ("mcDONALD", "mcD", "ONALD", Rule::ConsonantCluster, CaseClass::Lower, "ay"),
+            ("psst", "psst", "", Rule::NoVowel, CaseClass::Lower, "ay"),
+            ("HMM", "HMM", "", Rule::NoVowel, CaseClass::Upper, "AY"),
+        ];
+
+        for (word, onset, rime, rule, case, suffix) in expected {
+            let explanation = explain_word(word, &Style::WAY).unwrap();
+            assert_eq!((explanation.onset, explanation.rime, explanation.rule, explanation.case, explanation.suffix), (onset, rime, rule, case, suffix), "{}", word);
+            assert_eq!(explanation.pig_latin, crate::translate(word));
+        }
+
+        assert!(explain_word("", &Style::WAY).is_none());
+        assert!(explain_word("two words", &Style::WAY).is_none());
+        assert!(explain_word("café", &Style::WAY).is_none());
+        assert!(explain_word("well-known", &Style::WAY).is_none());
+    }
+
+    #[test]
+    fn test_explain_word_with_joiners() {
+        static HYPHENATED: crate::ByteClasses = crate::ByteClasses::ENGLISH.with_joiners(b"-");
+        let style = Style::WAY.with_classes(&HYPHENATED);
+
+        let explanation = explain_word("well-known", &style).unwrap();
+        assert_eq!((explanation.onset, explanation.rime, explanation.pig_latin.as_str()), ("w", "ell-known", "ell-knownway"));
+        let explanation = explain_word("sh-sh", &style).unwrap();
+        assert_eq!((explanation.onset, explanation.rule, explanation.pig_latin.as_str()), ("sh-sh", Rule::NoVowel, "sh-shay"));
+
+        assert!(explain_word("-well", &style).is_none());
+        assert!(explain_word("well-", &style).is_none());
+        assert!(explain_word("well--known", &style).is_none());
+    }
+
+    #[test]
+    fn test_explain_word_rules_versions() {
+        let v0_4 = Style::WAY.with_rules(RulesVersion::V0_4);
+
+        let explanation = explain_word("mcDONALD", &v0_4).unwrap();
+        assert_eq!((explanation.case, explanation.suffix, explanation.pig_latin.as_str()), (CaseClass::Upper, "AY", "ONALDmcDAY"));
+
+        let explanation = explain_word("ISn", &v0_4).unwrap();
+        assert_eq!((explanation.case, explanation.suffix, explanation.pig_latin.as_str()), (CaseClass::Title, "way", "ISnway"));
+    }
+}